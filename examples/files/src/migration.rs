@@ -4,7 +4,7 @@
 //! your database schema.
 
 use crate::{models, settings};
-use green_barrel::{Caching, Main, Monitor};
+use green_barrel::{Caching, Main, Monitor, NapalmPolicy};
 use mongodb::Client;
 use std::error::Error;
 
@@ -18,6 +18,8 @@ pub async fn run_migration(client: &Client) -> Result<(), Box<dyn Error>> {
         unique_app_key: settings::UNIQUE_APP_KEY,
         // For register models.
         model_key_list: vec![models::User::key()?],
+        // Policy for collections left without a model.
+        napalm_policy: NapalmPolicy::Refuse,
     };
     monitor.migrat(client).await?;
 
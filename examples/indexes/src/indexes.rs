@@ -1,4 +1,9 @@
 //! Indexes.
+//!
+//! Indexes declared in the models are created by `migrat()`:
+//! the field attributes `unique`, `index`, `sparse`, `ttl`
+//! and the model parameters `indexes`, `unique_indexes`.
+//! Indexes created manually ( names without the `gb__` prefix ) are not touched by the migration.
 
 use green_barrel::QCommons;
 use mongodb::{bson::doc, options::IndexOptions, Client, IndexModel};
@@ -6,15 +11,14 @@ use std::error::Error;
 
 use crate::models;
 
-// Create indexes
+// Create indexes that are not declared in the models
 pub async fn run_indexion(client: &Client) -> Result<(), Box<dyn Error>> {
     // Create index for User
     let options = IndexOptions::builder()
-        .unique(true)
-        .name("usernameIdx".to_string())
+        .name("slugCreatedAtIdx".to_string())
         .build();
     let index = IndexModel::builder()
-        .keys(doc! { "username": 1 })
+        .keys(doc! { "slug": 1, "created_at": -1 })
         .options(options)
         .build();
     models::User::create_index(client, index, None).await?;
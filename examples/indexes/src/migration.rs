@@ -3,7 +3,7 @@
 //! your models (adding a field, deleting a model, etc.) into
 //! your database schema.
 
-use green_barrel::{Caching, Main, Monitor};
+use green_barrel::{Caching, Main, Monitor, NapalmPolicy};
 use mongodb::Client;
 use std::error::Error;
 
@@ -19,6 +19,8 @@ pub async fn run_migration(client: &Client) -> Result<(), Box<dyn Error>> {
         unique_app_key: settings::UNIQUE_APP_KEY,
         // For register models.
         model_key_list: vec![models::User::key()?],
+        // Policy for collections left without a model.
+        napalm_policy: NapalmPolicy::Refuse,
     };
     monitor.migrat(client).await?;
 
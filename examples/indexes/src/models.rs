@@ -6,7 +6,7 @@ use crate::settings::{
     accounts::SERVICE_NAME, APP_NAME, DATABASE_NAME, DB_QUERY_DOCS_LIMIT, UNIQUE_APP_KEY,
 };
 
-#[Model()]
+#[Model(indexes = "email:1, username:-1")]
 #[derive(Serialize, Deserialize, Default, Debug)]
 pub struct User {
     pub username: TextField,
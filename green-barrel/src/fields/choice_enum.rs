@@ -0,0 +1,103 @@
+//! Type of selective field with static of elements.
+//! The elements are taken from a Rust enum ( `#[derive(ChoiceEnum)]` ).
+
+use core::fmt::Debug;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// Values and labels of the enum variants.
+/// Hint: Use `#[derive(ChoiceEnum)]` from the `metamorphose` crate.
+pub trait ChoiceEnum: Sized {
+    /// Format: [(value, Title), ...]
+    fn choices() -> Vec<(String, String)>;
+    /// Get the value of the variant ( stored in the database ).
+    fn value(&self) -> String;
+    /// Get the variant from the value.
+    fn from_value(value: &str) -> Option<Self>;
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(bound = "")]
+pub struct ChoiceEnumField<E: ChoiceEnum> {
+    /// The value is determined automatically.
+    /// Format: "model-name--field-name".
+    pub id: String,
+    /// Web form field name.
+    pub label: String,
+    /// Field type.
+    pub field_type: String,
+    /// The value is determined automatically.
+    pub name: String,
+    /// Sets the value of an element.
+    pub value: Option<String>,
+    /// Value by default.
+    /// Example: `Some(Color::Red.value())`.
+    pub default: Option<String>,
+    /// Displays prompt text.
+    pub placeholder: String,
+    /// Mandatory field.
+    pub required: bool,
+    /// Blocks access and modification of the element.
+    pub disabled: bool,
+    /// Specifies that the field cannot be modified by the user.
+    pub readonly: bool,
+    /// Html tag: <option value="value">Title</option>.
+    /// The value is determined automatically ( from the enum variants ).
+    pub choices: Vec<(String, String)>,
+    /// Hide field from user.
+    pub is_hide: bool,
+    /// Example: `r# "autofocus tabindex="some number" size="some number"#`.
+    pub other_attrs: String,
+    /// Example: "class-name-1 class-name-2".
+    pub css_classes: String,
+    /// Additional explanation for the user.
+    pub hint: String,
+    /// Warning information.
+    pub warning: String,
+    /// The value is determined automatically.
+    pub errors: Vec<String>,
+    /// To optimize field traversal in the `paladins/check()` method.
+    /// Hint: It is recommended not to change.
+    pub group: u32,
+    #[serde(skip)]
+    enum_type: PhantomData<E>,
+}
+
+impl<E: ChoiceEnum> Default for ChoiceEnumField<E> {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            label: String::new(),
+            field_type: String::from("ChoiceEnumField"),
+            name: String::new(),
+            value: None,
+            default: None,
+            placeholder: String::new(),
+            required: false,
+            disabled: false,
+            readonly: false,
+            choices: E::choices(),
+            is_hide: false,
+            other_attrs: String::new(),
+            css_classes: String::new(),
+            hint: String::new(),
+            warning: String::new(),
+            errors: Vec::new(),
+            group: 4,
+            enum_type: PhantomData,
+        }
+    }
+}
+
+impl<E: ChoiceEnum> ChoiceEnumField<E> {
+    // Getter
+    pub fn get(&self) -> Option<E> {
+        self.value
+            .as_ref()
+            .and_then(|value| E::from_value(value.as_str()))
+    }
+    // Setter
+    pub fn set(&mut self, value: E) {
+        self.value = Some(value.value());
+    }
+}
@@ -15,6 +15,8 @@ pub struct ChoiceTextMultField {
     pub placeholder: String, // Displays prompt text.
     pub required: bool, // Mandatory field.
     pub unique: bool, // The unique value of a field in a collection.
+    pub index: bool, // Create an index on the field ( speeds up filtering and sorting ).
+    pub sparse: bool, // The index skips documents that do not contain the field.
     pub disabled: bool, // Blocks access and modification of the element.
     pub readonly: bool, // Specifies that the field cannot be modified by the user.
     pub multiple: String, // Specifies that multiple options can be selected at once. Changing the default value is not recommended.
@@ -41,6 +43,8 @@ impl Default for ChoiceTextMultField {
             placeholder: String::new(),
             required: false,
             unique: false,
+            index: false,
+            sparse: false,
             disabled: false,
             readonly: false,
             // Changing the default value is not recommended.
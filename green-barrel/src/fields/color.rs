@@ -19,6 +19,8 @@ pub struct ColorField {
     pub maxlength: usize, // The maximum number of characters allowed in the text.
     pub required: bool, // Mandatory field.
     pub unique: bool, // The unique value of a field in a collection.
+    pub index: bool, // Create an index on the field ( speeds up filtering and sorting ).
+    pub sparse: bool, // The index skips documents that do not contain the field.
     pub disabled: bool, // Blocks access and modification of the element.
     pub readonly: bool, // Specifies that the field cannot be modified by the user.
     pub is_hide: bool, // Hide field from user.
@@ -46,6 +48,8 @@ impl Default for ColorField {
             maxlength: 256,
             required: false,
             unique: false,
+            index: false,
+            sparse: false,
             disabled: false,
             readonly: false,
             is_hide: false,
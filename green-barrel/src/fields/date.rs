@@ -15,6 +15,9 @@ pub struct DateField {
     pub placeholder: String, // Displays prompt text.
     pub required: bool, // Mandatory field.
     pub unique: bool, // The unique value of a field in a collection.
+    pub index: bool, // Create an index on the field ( speeds up filtering and sorting ).
+    pub sparse: bool, // The index skips documents that do not contain the field.
+    pub ttl: u32, // Lifetime of the document in seconds after the date ( TTL index, 0 - disabled ).
     pub disabled: bool, // Blocks access and modification of the element.
     pub readonly: bool, // Specifies that the field cannot be modified by the user.
     pub min: String, // The lower value for entering a date.
@@ -42,6 +45,9 @@ impl Default for DateField {
             placeholder: String::new(),
             required: false,
             unique: false,
+            index: false,
+            sparse: false,
+            ttl: 0,
             disabled: false,
             readonly: false,
             min: String::new(),
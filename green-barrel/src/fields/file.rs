@@ -19,13 +19,17 @@ pub struct FileField {
     pub default: Option<FileData>, // Value by default
     pub media_root: String, // Root partition for storing files.
     pub media_url: String, // Url address to the root section.
+    pub storage: String, // Name of the registered storage backend or "gridfs". Empty string - local file system ( media_root/media_url ).
     pub target_dir: String, // Directory for files inside media directory (inner path). Example: "files/resume".
     pub accept: String,     // Example: "image/jpeg,image/png,image/gif"
-    pub placeholder: String, // Displays prompt text.
-    pub required: bool,     // Mandatory field.
-    pub disabled: bool,     // Blocks access and modification of the element.
-    pub readonly: bool,     // Specifies that the field cannot be modified by the user.
-    pub is_hide: bool,      // Hide field from user.
+    pub max_size: u64,      // Maximum file size in bytes. Hint: 0 - no limit.
+    pub allowed_types: Vec<String>, // Allowed MIME types, determined by the contents of the file. Example: vec!["application/pdf".into(), "image/*".into()]. Hint: Empty - any type; text files without magic bytes - "text/plain".
+    pub is_content_addressed: bool, // Store files by content hash ( SHA-256 ) - identical uploads share one file. Hint: The file is deleted when the last reference goes away.
+    pub placeholder: String,        // Displays prompt text.
+    pub required: bool,             // Mandatory field.
+    pub disabled: bool,             // Blocks access and modification of the element.
+    pub readonly: bool,             // Specifies that the field cannot be modified by the user.
+    pub is_hide: bool,              // Hide field from user.
     /// Example: `r# "autofocus tabindex="some number" size="some number"#`.    
     pub other_attrs: String,
     pub css_classes: String, // Example: "class-name-1 class-name-2".
@@ -47,8 +51,12 @@ impl Default for FileField {
             default: None,
             media_root: String::from("./resources/media"),
             media_url: String::from("/media"),
+            storage: String::new(),
             target_dir: String::from("files"),
             accept: String::new(),
+            max_size: 0,
+            allowed_types: Vec::new(),
+            is_content_addressed: false,
             placeholder: String::new(),
             required: false,
             disabled: false,
@@ -87,6 +95,7 @@ impl FileField {
         });
     }
     // Copy file to media_root}/tmp directory
+    // Hint: The temporary directory is always on the local file system.
     pub fn copy_file_to_tmp(
         file_path: &str,
         media_root: Option<&str>,
@@ -29,6 +29,7 @@ pub struct HashField {
     pub errors: Vec<String>, // The value is determined automatically.
     pub alert: String, // Alert message for the entire web form. The value is determined automatically.
     pub group: u32, // To optimize field traversal in the `paladins/check()` method. Hint: It is recommended not to change.
+    pub id_type: String, // "ObjectId|UUID|String|I64" - The value is determined automatically.
 }
 
 impl Default for HashField {
@@ -55,6 +56,7 @@ impl Default for HashField {
             errors: Vec::new(),
             alert: String::new(),
             group: 1,
+            id_type: String::from("ObjectId"),
         }
     }
 }
@@ -68,6 +70,10 @@ impl HashField {
     }
 
     pub fn obj_id(&self) -> Result<Option<ObjectId>, Box<dyn Error>> {
+        // Only for `id_type = "ObjectId"` the hash-line can be converted to ObjectId.
+        if self.id_type != "ObjectId" {
+            return Ok(None);
+        }
         let hash = self.value.clone().unwrap_or_default();
         if let Ok(obj_id) = ObjectId::parse_str(hash.as_str()) {
             return Ok(Some(obj_id));
@@ -16,6 +16,8 @@ pub struct HiddenDateTimeField {
     pub placeholder: String, // Displays prompt text.
     pub required: bool, // Mandatory field.
     pub unique: bool, // The unique value of a field in a collection.
+    pub index: bool, // Create an index on the field ( speeds up filtering and sorting ).
+    pub sparse: bool, // The index skips documents that do not contain the field.
     pub disabled: bool, // Blocks access and modification of the element.
     pub readonly: bool, // Specifies that the field cannot be modified by the user.
     pub min: String, // The lower value for entering a date and time.
@@ -43,6 +45,8 @@ impl Default for HiddenDateTimeField {
             placeholder: String::new(),
             required: false,
             unique: false,
+            index: false,
+            sparse: false,
             disabled: true,
             readonly: false,
             min: String::new(),
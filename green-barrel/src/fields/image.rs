@@ -29,11 +29,24 @@ pub struct ImageField {
     pub media_root: String,
     /// Url address to the root section.
     pub media_url: String,
+    /// Name of the registered storage backend or "gridfs" ( GridFS bucket in the database of the Model ).
+    /// Empty string - local file system ( `media_root`/`media_url` ).
+    pub storage: String,
     /// Directory for images inside media directory (inner path).
     /// Example: "images/avatars".
     pub target_dir: String,
     /// Example: "image/jpeg,image/png,image/gif"
     pub accept: String,
+    /// Maximum file size in bytes.
+    /// Hint: 0 - no limit.
+    pub max_size: u64,
+    /// Allowed MIME types, determined by the contents of the file.
+    /// Example: `vec!["image/jpeg".into(), "image/png".into()]`.
+    /// Hint: Empty - any image type.
+    pub allowed_types: Vec<String>,
+    /// Store images by content hash ( SHA-256 ) - identical uploads share one image and thumbnails.
+    /// Hint: The image is deleted when the last reference goes away.
+    pub is_content_addressed: bool,
     /// Displays prompt text.
     pub placeholder: String,
     /// Mandatory field.
@@ -44,10 +57,51 @@ pub struct ImageField {
     pub readonly: bool,
     /// From one to four inclusive.
     /// Example: `vec![("xs", 150),("sm", 300),("md", 600),("lg", 1200)]`.
-    /// Hint: An Intel i7-4770 processor or better is recommended.
+    /// Hint: Thumbnails are created in parallel in a blocking thread pool.
     pub thumbnails: Vec<(String, u32)>,
+    /// Minimum image width in pixels.
+    /// Hint: 0 - no limit.
+    pub min_width: u32,
+    /// Minimum image height in pixels.
+    /// Hint: 0 - no limit.
+    pub min_height: u32,
+    /// Maximum image width in pixels ( protection against decompression bombs ).
+    /// Hint: Checked before decoding the image. 0 - no limit. Default = 16384.
+    pub max_width: u32,
+    /// Maximum image height in pixels ( protection against decompression bombs ).
+    /// Hint: Checked before decoding the image. 0 - no limit. Default = 16384.
+    pub max_height: u32,
+    /// Minimum aspect ratio of the image ( width / height ).
+    /// Example: 1.0 - landscape or square only. Hint: 0.0 - no limit.
+    pub min_aspect_ratio: f64,
+    /// Maximum aspect ratio of the image ( width / height ).
+    /// Example: 1.0 - portrait or square only. Hint: 0.0 - no limit.
+    pub max_aspect_ratio: f64,
     /// Create thumbnails - Fast=false or qualitatively=true? Default = true.
     pub is_quality: bool,
+    /// Rotate the image according to the EXIF orientation ( photos from phones ).
+    /// Default = true.
+    pub is_auto_orient: bool,
+    /// Remove metadata ( EXIF, GPS, etc. ) from the original image.
+    /// Hint: The original image is re-encoded. Thumbnails never contain metadata.
+    pub is_strip_metadata: bool,
+    /// Format of the original image and thumbnails.
+    /// Valid values: "" - source format, "jpeg", "png", "webp" ( lossless ).
+    pub output_format: String,
+    /// JPEG quality from 1 to 100. Default = 80.
+    pub jpeg_quality: u8,
+    /// How thumbnails are fitted to size.
+    /// Valid values: "scale" - scale to fit ( default ), "fill" - crop to fill the aspect ratio,
+    /// "pad" - scale to fit and pad to the aspect ratio.
+    pub resize_mode: String,
+    /// Aspect ratio of thumbnails for the "fill" and "pad" modes ( width, height ).
+    /// Example: `(16, 9)`. Default = `(1, 1)`.
+    pub aspect_ratio: (u32, u32),
+    /// Background color for the "pad" mode ( RGBA ).
+    /// Default = `[255, 255, 255, 255]`.
+    pub pad_color: [u8; 4],
+    /// Compute placeholders for the frontend - `blurhash` and `dominant_color` in ImageData.
+    pub is_placeholder: bool,
     /// Hide field from user.
     pub is_hide: bool,
     /// Example: `r# "autofocus tabindex="some number" size="some number"#`.    
@@ -77,14 +131,32 @@ impl Default for ImageField {
             default: None,
             media_root: String::from("./resources/media"),
             media_url: String::from("/media"),
+            storage: String::new(),
             target_dir: String::from("images"),
             accept: String::new(),
+            max_size: 0,
+            allowed_types: Vec::new(),
+            is_content_addressed: false,
             placeholder: String::new(),
             required: false,
             disabled: false,
             readonly: false,
             thumbnails: Vec::new(),
+            min_width: 0,
+            min_height: 0,
+            max_width: 16384,
+            max_height: 16384,
+            min_aspect_ratio: 0.0,
+            max_aspect_ratio: 0.0,
             is_quality: true,
+            is_auto_orient: true,
+            is_strip_metadata: false,
+            output_format: String::new(),
+            jpeg_quality: 80,
+            resize_mode: String::from("scale"),
+            aspect_ratio: (1, 1),
+            pad_color: [255, 255, 255, 255],
+            is_placeholder: false,
             is_hide: false,
             other_attrs: String::new(),
             css_classes: String::new(),
@@ -121,6 +193,7 @@ impl ImageField {
         });
     }
     // Copy file to media_root}/tmp directory
+    // Hint: The temporary directory is always on the local file system.
     pub fn copy_file_to_tmp(
         image_path: &str,
         media_root: Option<&str>,
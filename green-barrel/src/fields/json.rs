@@ -0,0 +1,89 @@
+//! A field for storing an arbitrary **JSON** document or array.
+//! In the database, the value is stored as a BSON document or BSON array.
+
+use core::fmt::Debug;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JsonField {
+    /// The value is determined automatically.
+    /// Format: "model-name--field-name".
+    pub id: String,
+    /// Web form field name.
+    pub label: String,
+    /// Field type.
+    pub field_type: String,
+    /// The value is determined automatically.
+    pub input_type: String,
+    /// The value is determined automatically.
+    pub name: String,
+    /// Sets the value of an element.
+    /// Hint: Only a JSON object or a JSON array.
+    pub value: Option<Value>,
+    /// Value by default.
+    pub default: Option<Value>,
+    /// Displays prompt text.
+    pub placeholder: String,
+    /// JSON Schema to validate the value.
+    /// Example: `r#"{"type": "object", "required": ["name"]}"#`.
+    pub json_schema: String,
+    /// Mandatory field.
+    pub required: bool,
+    /// Blocks access and modification of the element.
+    pub disabled: bool,
+    /// Specifies that the field cannot be modified by the user.
+    pub readonly: bool,
+    /// Hide field from user.
+    pub is_hide: bool,
+    /// Example: `r# "autofocus tabindex="some number" size="some number"#`.
+    pub other_attrs: String,
+    /// Example: "class-name-1 class-name-2".
+    pub css_classes: String,
+    /// Additional explanation for the user.
+    pub hint: String,
+    /// Warning information.
+    pub warning: String,
+    /// The value is determined automatically.
+    pub errors: Vec<String>,
+    /// To optimize field traversal in the `paladins/check()` method.
+    /// Hint: It is recommended not to change.
+    pub group: u32,
+}
+
+impl Default for JsonField {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            label: String::new(),
+            field_type: String::from("JsonField"),
+            input_type: String::from("textarea"),
+            name: String::new(),
+            value: None,
+            default: None,
+            placeholder: String::new(),
+            json_schema: String::new(),
+            required: false,
+            disabled: false,
+            readonly: false,
+            is_hide: false,
+            other_attrs: String::new(),
+            css_classes: String::new(),
+            hint: String::new(),
+            warning: String::new(),
+            errors: Vec::new(),
+            group: 17,
+        }
+    }
+}
+
+impl JsonField {
+    // Getter
+    pub fn get(&self) -> Option<Value> {
+        self.value.clone()
+    }
+    // Setter
+    pub fn set(&mut self, value: Value) {
+        self.value = Some(value);
+    }
+}
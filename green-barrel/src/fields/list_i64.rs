@@ -0,0 +1,108 @@
+//! A field for entering a list of numbers of type **i64**.
+//! In the database, the value is stored as a BSON array.
+
+use core::fmt::Debug;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ListI64Field {
+    /// The value is determined automatically.
+    /// Format: "model-name--field-name".
+    pub id: String,
+    /// Web form field name.
+    pub label: String,
+    /// Field type.
+    pub field_type: String,
+    /// The value is determined automatically.
+    pub input_type: String,
+    /// The value is determined automatically.
+    pub name: String,
+    /// Sets the value of an element.
+    pub value: Option<Vec<i64>>,
+    /// Value by default.
+    pub default: Option<Vec<i64>>,
+    /// Displays prompt text.
+    pub placeholder: String,
+    /// The lower value for each item.
+    pub min: i64,
+    /// The top value for each item.
+    pub max: i64,
+    /// The minimum number of items.
+    pub min_items: usize,
+    /// The maximum number of items.
+    pub max_items: usize,
+    /// Remove duplicate items.
+    pub is_dedup: bool,
+    /// The unique value of each item in a collection.
+    pub unique: bool,
+    /// Create an index on the field ( speeds up filtering and sorting ).
+    pub index: bool,
+    /// The index skips documents that do not contain the field.
+    pub sparse: bool,
+    /// Mandatory field.
+    /// Hint: An empty list is not accepted.
+    pub required: bool,
+    /// Blocks access and modification of the element.
+    pub disabled: bool,
+    /// Specifies that the field cannot be modified by the user.
+    pub readonly: bool,
+    /// Hide field from user.
+    pub is_hide: bool,
+    /// Example: `r# "autofocus tabindex="some number" size="some number"#`.
+    pub other_attrs: String,
+    /// Example: "class-name-1 class-name-2".
+    pub css_classes: String,
+    /// Additional explanation for the user.
+    pub hint: String,
+    /// Warning information.
+    pub warning: String,
+    /// The value is determined automatically.
+    pub errors: Vec<String>,
+    /// To optimize field traversal in the `paladins/check()` method.
+    /// Hint: It is recommended not to change.
+    pub group: u32,
+}
+
+impl Default for ListI64Field {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            label: String::new(),
+            field_type: String::from("ListI64Field"),
+            input_type: String::from("number"),
+            name: String::new(),
+            value: None,
+            default: None,
+            placeholder: String::new(),
+            min: i64::MIN,
+            max: i64::MAX,
+            min_items: 0,
+            max_items: 256,
+            is_dedup: false,
+            unique: false,
+            index: false,
+            sparse: false,
+            required: false,
+            disabled: false,
+            readonly: false,
+            is_hide: false,
+            other_attrs: String::new(),
+            css_classes: String::new(),
+            hint: String::new(),
+            warning: String::new(),
+            errors: Vec::new(),
+            group: 16,
+        }
+    }
+}
+
+impl ListI64Field {
+    // Getter
+    pub fn get(&self) -> Option<Vec<i64>> {
+        self.value.clone()
+    }
+    // Setter
+    pub fn set(&mut self, value: Vec<i64>) {
+        self.value = Some(value);
+    }
+}
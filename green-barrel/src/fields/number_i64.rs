@@ -27,6 +27,10 @@ pub struct I64Field {
     pub required: bool,
     /// The unique value of a field in a collection.
     pub unique: bool,
+    /// Create an index on the field ( speeds up filtering and sorting ).
+    pub index: bool,
+    /// The index skips documents that do not contain the field.
+    pub sparse: bool,
     /// Blocks access and modification of the element.
     pub disabled: bool,
     /// Specifies that the field cannot be modified by the user.
@@ -70,6 +74,8 @@ impl Default for I64Field {
             placeholder: String::new(),
             required: false,
             unique: false,
+            index: false,
+            sparse: false,
             disabled: false,
             readonly: false,
             step: 1,
@@ -20,6 +20,9 @@ pub struct PasswordField {
     pub disabled: bool, // Blocks access and modification of the element.
     pub readonly: bool, // Specifies that the field cannot be modified by the user.
     pub is_hide: bool, // Hide field from user.
+    pub min_char_classes: usize, // Minimum number of character classes - lowercase, uppercase, digits, other. Hint: 0 - no check.
+    pub is_deny_common: bool,    // Prohibit common passwords ( built-in denylist ).
+    pub username_field: String, // Name of the field with the username, which the password must not contain. Hint: Empty string - no check.
     /// Example: `r# "autofocus tabindex="some number" size="some number"#`.    
     pub other_attrs: String,
     pub css_classes: String, // Example: "class-name-1 class-name-2".
@@ -50,6 +53,9 @@ impl Default for PasswordField {
             disabled: false,
             readonly: false,
             is_hide: false,
+            min_char_classes: 0,
+            is_deny_common: false,
+            username_field: String::new(),
             other_attrs: String::new(),
             css_classes: String::new(),
             hint: String::new(),
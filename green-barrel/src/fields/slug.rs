@@ -14,6 +14,8 @@ pub struct SlugField {
     pub value: Option<String>, // Sets the value of an element.
     pub placeholder: String, // Displays prompt text.
     pub unique: bool, // The unique value of a field in a collection.
+    pub index: bool, // Create an index on the field ( speeds up filtering and sorting ).
+    pub sparse: bool, // The index skips documents that do not contain the field.
     pub disabled: bool, // Blocks access and modification of the element.
     pub readonly: bool, // Specifies that the field cannot be modified by the user.
     pub slug_sources: Vec<String>, // Example: vec!["title"] or vec!["hash", "username"] or vec!["email", "first_name", "last_name"].
@@ -38,6 +40,8 @@ impl Default for SlugField {
             value: None,
             placeholder: String::new(),
             unique: true,
+            index: false,
+            sparse: false,
             disabled: false,
             readonly: true,
             slug_sources: vec!["hash".into()],
@@ -0,0 +1,99 @@
+//! A field for entering a time of day in the format **00:00**.
+
+use core::fmt::Debug;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TimeField {
+    /// The value is determined automatically.
+    /// Format: "model-name--field-name".
+    pub id: String,
+    /// Web form field name.
+    pub label: String,
+    /// Field type.
+    pub field_type: String,
+    /// The value is determined automatically.
+    pub input_type: String,
+    /// The value is determined automatically.
+    pub name: String,
+    /// Sets the value of an element.
+    /// Example: 23:59 or 23:59:59
+    pub value: Option<String>,
+    /// Value by default.
+    /// Example: 23:59 or 23:59:59
+    pub default: Option<String>,
+    /// Displays prompt text.
+    pub placeholder: String,
+    /// Mandatory field.
+    pub required: bool,
+    /// The unique value of a field in a collection.
+    pub unique: bool,
+    /// Create an index on the field ( speeds up filtering and sorting ).
+    pub index: bool,
+    /// The index skips documents that do not contain the field.
+    pub sparse: bool,
+    /// Blocks access and modification of the element.
+    pub disabled: bool,
+    /// Specifies that the field cannot be modified by the user.
+    pub readonly: bool,
+    /// The lower value for entering a time.
+    pub min: String,
+    /// The top value for entering a time.
+    pub max: String,
+    /// Hide field from user.
+    pub is_hide: bool,
+    /// Example: `r# "autofocus tabindex="some number" size="some number"#`.
+    pub other_attrs: String,
+    /// Example: "class-name-1 class-name-2".
+    pub css_classes: String,
+    /// Additional explanation for the user.
+    pub hint: String,
+    /// Warning information.
+    pub warning: String,
+    /// The value is determined automatically.
+    pub errors: Vec<String>,
+    /// To optimize field traversal in the `paladins/check()` method.
+    /// Hint: It is recommended not to change.
+    pub group: u32,
+}
+
+impl Default for TimeField {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            label: String::new(),
+            field_type: String::from("TimeField"),
+            input_type: String::from("time"),
+            name: String::new(),
+            value: None,
+            default: None,
+            placeholder: String::new(),
+            required: false,
+            unique: false,
+            index: false,
+            sparse: false,
+            disabled: false,
+            readonly: false,
+            min: String::new(),
+            max: String::new(),
+            is_hide: false,
+            other_attrs: String::new(),
+            css_classes: String::new(),
+            hint: t!("format", sample = "hh:mm"),
+            warning: String::new(),
+            errors: Vec::new(),
+            group: 15,
+        }
+    }
+}
+
+impl TimeField {
+    // Getter
+    pub fn get(&self) -> Option<String> {
+        self.value.clone()
+    }
+    // Setter
+    pub fn set(&mut self, value: &str) {
+        self.value = Some(String::from(value));
+    }
+}
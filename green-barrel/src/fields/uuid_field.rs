@@ -0,0 +1,107 @@
+//! A field for storing a **UUID**.
+//! In the database, the value is stored as BSON binary subtype 4.
+
+use core::fmt::Debug;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UUIDField {
+    /// The value is determined automatically.
+    /// Format: "model-name--field-name".
+    pub id: String,
+    /// Web form field name.
+    pub label: String,
+    /// Field type.
+    pub field_type: String,
+    /// For Html `<input type="text|hidden">`.
+    pub input_type: String,
+    /// The value is determined automatically.
+    pub name: String,
+    /// Sets the value of an element.
+    /// Example: "67e55044-10b1-426f-9247-bb680e5fe0c8"
+    pub value: Option<String>,
+    /// Value by default.
+    pub default: Option<String>,
+    /// Displays prompt text.
+    pub placeholder: String,
+    /// Mandatory field.
+    pub required: bool,
+    /// The unique value of a field in a collection.
+    pub unique: bool,
+    /// Create an index on the field ( speeds up filtering and sorting ).
+    pub index: bool,
+    /// The index skips documents that do not contain the field.
+    pub sparse: bool,
+    /// Blocks access and modification of the element.
+    pub disabled: bool,
+    /// Specifies that the field cannot be modified by the user.
+    pub readonly: bool,
+    /// Generate a new UUID (version 4) if the field is empty.
+    pub is_auto: bool,
+    /// Hide field from user.
+    pub is_hide: bool,
+    /// Example: `r# "autofocus tabindex="some number" size="some number"#`.
+    pub other_attrs: String,
+    /// Example: "class-name-1 class-name-2".
+    pub css_classes: String,
+    /// Additional explanation for the user.
+    pub hint: String,
+    /// Warning information.
+    pub warning: String,
+    /// The value is determined automatically.
+    pub errors: Vec<String>,
+    /// To optimize field traversal in the `paladins/check()` method.
+    /// Hint: It is recommended not to change.
+    pub group: u32,
+}
+
+impl Default for UUIDField {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            label: String::new(),
+            field_type: String::from("UUIDField"),
+            input_type: String::from("text"),
+            name: String::new(),
+            value: None,
+            default: None,
+            placeholder: String::new(),
+            required: false,
+            unique: false,
+            index: false,
+            sparse: false,
+            disabled: false,
+            readonly: false,
+            is_auto: false,
+            is_hide: false,
+            other_attrs: String::new(),
+            css_classes: String::new(),
+            hint: t!("format", sample = "xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx"),
+            warning: String::new(),
+            errors: Vec::new(),
+            group: 14,
+        }
+    }
+}
+
+impl UUIDField {
+    // Getter
+    pub fn get(&self) -> Option<String> {
+        self.value.clone()
+    }
+    // Setter
+    pub fn set(&mut self, value: &str) {
+        self.value = Some(String::from(value));
+    }
+
+    // Get the value as a `Uuid`.
+    pub fn uuid(&self) -> Option<Uuid> {
+        let value = self.value.clone().unwrap_or_default();
+        Uuid::parse_str(value.as_str()).ok()
+    }
+    // Set the value from a `Uuid`.
+    pub fn set_uuid(&mut self, uuid: Uuid) {
+        self.value = Some(uuid.hyphenated().to_string());
+    }
+}
@@ -16,8 +16,17 @@
 //! | is_up_doc           | true         | Update documents in the database.                                                                    |
 //! | is_del_doc          | true         | Delete documents from the database.                                                                  |
 //! | ignore_fields       | empty string | Fields that are not included in the database (separated by commas).                                  |
+//! | renamed_fields      | empty string | Renamed fields, migration moves the values (separated by commas). Format: `old_name:new_name`.       |
+//! | indexes             | empty string | Compound indexes (separated by semicolons). Format: `field_name:1, field_name:-1; field_name`.       |
+//! | unique_indexes      | empty string | Compound unique indexes, the format is the same as for `indexes`.                                    |
+//! | validation_level    | moderate     | How the `$jsonSchema` validator applies to existing documents - off, strict, moderate.               |
+//! | validation_action   | error        | Action for documents that fail the `$jsonSchema` validator - error, warn.                            |
 //! | is_use_addition     | false        | Allows methods for additional actions and additional validation.                                     |
 //! | is_use_hooks        | false        | Allows hooks methods - **impl Hooks for ModelName**.                                                 |
+//! | id_type             | ObjectId     | Type of document identifier `_id` - ObjectId, UUID, String, I64 ( String, I64 - set via `hash` ).    |
+//! | argon2_variant      | empty string | Argon2 variant for `PasswordField` - argon2d, argon2i, argon2id ( empty string - global parameter ). |
+//! | argon2_mem_cost     | 0            | Argon2 memory cost in KiB for `PasswordField` ( 0 - global parameter, default 19456 ).               |
+//! | argon2_time_cost    | 0            | Argon2 number of iterations for `PasswordField` ( 0 - global parameter, default 2 ).                 |
 //!
 
 #[macro_use]
@@ -28,12 +37,24 @@ i18n!("locales");
 pub mod fields;
 pub mod migration;
 pub mod models;
+pub mod storage;
 pub mod store;
 pub mod test_tool;
 
 pub use crate::{
     fields::*,
-    migration::Monitor,
+    migration::{
+        convert::{register_field_converter, FieldConverter, UnconvertibleValue},
+        data::{DataMigration, DATA_MIGRATIONS},
+        indexes::{declared_indexes, duplicate_key_fields, INDEX_PREFIX},
+        lock::{MigrationLock, MIGRATION_LOCKS, MIGRATION_LOCK_TTL, MIGRAT_LOCK},
+        rewrite::{
+            set_rewrite_progress_callback, RewriteProgress, RewriteProgressCallback,
+            MIGRATION_PROGRESS, REWRITE_BATCH_SIZE,
+        },
+        validator::json_schema_validator,
+        MediaGcReport, MigrationReport, ModelReport, Monitor, NapalmPolicy, MEDIA_GC_MIN_AGE,
+    },
     models::{
         addition::Addition,
         caching::Caching,
@@ -41,12 +62,20 @@ pub use crate::{
         converters::Converters,
         db_query_api::{commons::QCommons, paladins::QPaladins},
         fixtures::Fixtures,
-        helpers::{ControlArr, FileData, ImageData, Meta},
+        helpers::{ControlArr, FileData, IdType, ImageData, Meta},
         hooks::Hooks,
         output_data::{OutputData, OutputData2},
-        validation::Validation,
+        password::{hash_password, set_argon2_params, verify_password_hash, Argon2Params},
+        schema::{
+            model_json_schema, model_openapi_schema, openapi_components, JSON_SCHEMA_DIALECT,
+        },
+        validation::{check_password_strength, read_file_head, Validation, FILE_HEAD_SIZE},
         Main,
     },
-    store::METADATA,
+    storage::{
+        get_storage, register_storage, sha256_hex, GridFsStorage, LocalStorage, S3Storage, Storage,
+        GRIDFS_BUCKET, GRIDFS_STORAGE, MEDIA_REFS,
+    },
+    store::{ARGON2_PARAMS, FIELD_CONVERTERS, METADATA, STORAGES},
     test_tool::del_test_db,
 };
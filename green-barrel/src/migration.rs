@@ -3,6 +3,13 @@
 //! your models (adding a field, deleting a collection, etc.) into
 //! your database schema.
 
+pub mod convert;
+pub mod data;
+pub mod indexes;
+pub mod lock;
+pub mod rewrite;
+pub mod validator;
+
 use chrono::Utc;
 use futures::stream::TryStreamExt;
 use mongodb::{
@@ -11,21 +18,39 @@ use mongodb::{
         doc,
         document::Document,
         ser::{to_bson, to_document},
-        Bson,
+        spec::BinarySubtype,
+        Binary, Bson,
     },
+    options::FindOptions,
     Client, Database,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error::Error, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use uuid::Uuid;
 
 use crate::{
-    models::helpers::{FileData, ImageData},
+    migration::{
+        convert::{convert_value, get_field_converter, UnconvertibleValue},
+        indexes::sync_indexes,
+        lock::{MigrationLock, MIGRATION_LOCK_TTL, MIGRAT_LOCK},
+        rewrite::{report_progress, write_batch, Checkpoint, RewriteProgress, REWRITE_BATCH_SIZE},
+        validator::sync_validator,
+    },
+    models::helpers::{FileData, ImageData, Meta},
+    storage::MEDIA_REFS,
     store::METADATA,
 };
 
 // MIGRATION
 // #################################################################################################
+
 /// For creation and updating of a technical database.
 #[derive(Serialize, Deserialize)]
 pub struct ModelState {
@@ -36,11 +61,117 @@ pub struct ModelState {
     pub status: bool,
 }
 
+/// Minimum age of an orphaned media file for garbage collection ( by modification time ).
+/// Hint: Newer files can belong to a document that is being saved right now.
+pub const MEDIA_GC_MIN_AGE: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Result of the orphaned media garbage collection.
+#[derive(Default, Clone, Debug)]
+pub struct MediaGcReport {
+    /// Orphaned files were only reported, not removed.
+    pub is_dry_run: bool,
+    /// Number of scanned files in the dated directories.
+    pub scanned_count: usize,
+    /// Number of unreferenced files that are newer than the minimum age ( they are kept ).
+    pub recent_count: usize,
+    /// Paths to orphaned files.
+    pub orphans: Vec<String>,
+    /// Total size of orphaned files in bytes.
+    pub orphans_size: u64,
+}
+
+/// Result of the migration ( or the migration plan ).
+#[derive(Default, Clone, Debug)]
+pub struct MigrationReport {
+    /// The database was not changed ( `Monitor::plan()` ).
+    pub is_dry_run: bool,
+    /// Changes for each registered Model.
+    pub models: Vec<ModelReport>,
+    /// Orphaned collections ( left without a model ) - (database, collection).
+    pub orphaned_collections: Vec<(String, String)>,
+    /// Orphaned collections renamed into the archive namespace - (database, collection).
+    pub archived_collections: Vec<(String, String)>,
+    /// Values that could not be converted to the new field type
+    /// ( the original values are kept ).
+    pub unconvertible: Vec<UnconvertibleValue>,
+}
+
+impl MigrationReport {
+    /// Whether the migration changes the database
+    /// ( or leaves values that could not be converted ).
+    pub fn has_changes(&self) -> bool {
+        !self.orphaned_collections.is_empty()
+            || !self.unconvertible.is_empty()
+            || self.models.iter().any(|model| model.has_changes())
+    }
+}
+
+/// Changes in the collection of the Model.
+#[derive(Default, Clone, Debug)]
+pub struct ModelReport {
+    pub model_name: String,
+    pub database: String,
+    pub collection: String,
+    /// There is no state of the model in the technical database ( first migration ).
+    pub is_new_model: bool,
+    /// The collection does not exist and will be created.
+    pub is_new_collection: bool,
+    pub added_fields: Vec<String>,
+    pub removed_fields: Vec<String>,
+    /// (old_name, new_name)
+    pub renamed_fields: Vec<(String, String)>,
+    /// (field_name, old_type, new_type)
+    pub retyped_fields: Vec<(String, String, String)>,
+    /// Number of documents whose fields are moved ( `renamed_fields` ).
+    pub renamed_docs: u64,
+    /// Number of documents to be rewritten ( added and retyped fields ).
+    pub affected_docs: u64,
+    /// Fields of dynamic types, whose arrays of choices are reset ( the field type has changed ).
+    pub reset_dyn_fields: Vec<String>,
+    /// Names of the declared indexes that are created.
+    pub created_indexes: Vec<String>,
+    /// Names of the indexes that are no longer declared and are dropped.
+    pub dropped_indexes: Vec<String>,
+    /// The `$jsonSchema` validator of the collection ( or its options ) has changed.
+    pub is_validator_changed: bool,
+}
+
+impl ModelReport {
+    /// Whether the migration changes the collection of the Model.
+    pub fn has_changes(&self) -> bool {
+        self.is_new_model
+            || self.is_new_collection
+            || !self.added_fields.is_empty()
+            || !self.removed_fields.is_empty()
+            || !self.renamed_fields.is_empty()
+            || !self.retyped_fields.is_empty()
+            || !self.reset_dyn_fields.is_empty()
+            || !self.created_indexes.is_empty()
+            || !self.dropped_indexes.is_empty()
+            || self.is_validator_changed
+    }
+}
+
+/// Policy for collections left without a model ( not registered in `model_key_list` ).
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NapalmPolicy {
+    /// Drop the collections and their records in the technical database.
+    Drop,
+    /// Rename the collections into the archive namespace -
+    /// `archive__{collection}__{YYYYmmddHHMMSS}` in the same database.
+    Archive,
+    /// Do not touch the collections, `migrat()` returns an error listing them
+    /// ( before any changes to the database ).
+    #[default]
+    Refuse,
+}
+
 /// For monitoring the state of models.
 pub struct Monitor<'a> {
     pub app_name: &'a str,
     pub unique_app_key: &'a str,
     pub model_key_list: Vec<String>,
+    pub napalm_policy: NapalmPolicy,
 }
 
 impl<'a> Monitor<'a> {
@@ -94,24 +225,31 @@ impl<'a> Monitor<'a> {
         // Used to store selection items, for
         // Fields type like selectTextDyn, selectTextMultDyn, etc.
         let collection_dyn_fields_type: &str = "dynamic_fields";
-        //Get a list of databases.
-        let database_names = client.list_database_names(None, None).await?;
-        // Create a technical database for the project if it doesn't exist.
-        if !database_names.contains(&db_green_tech) {
+        let green_tech_db = client.database(&db_green_tech);
+        // Get a list of collections of the technical database.
+        // Hint: The technical database may already exist without these collections,
+        // for example, if it was created by the migration lock.
+        let collection_names = green_tech_db.list_collection_names(None).await?;
+        // Create a collection for fields types of `select`.
+        // (selectTextDyn, selectTextMultDyn, etc.)
+        if !collection_names
+            .iter()
+            .any(|name| name == collection_dyn_fields_type)
+        {
+            green_tech_db
+                .create_collection(collection_dyn_fields_type, None)
+                .await?;
+        }
+        if !collection_names
+            .iter()
+            .any(|name| name == collection_models_name)
+        {
             // Create a collection for models.
-            client
-                .database(&db_green_tech)
+            green_tech_db
                 .create_collection(collection_models_name, None)
                 .await?;
-            // Create a collection for fields types of `select`.
-            // (selectTextDyn, selectTextMultDyn, etc.)
-            client
-                .database(&db_green_tech)
-                .create_collection(collection_dyn_fields_type, None)
-                .await?;
         } else {
             // Reset models state information.
-            let green_tech_db = client.database(&db_green_tech);
             let collection_models = green_tech_db.collection::<Document>(collection_models_name);
             let mut cursor = collection_models.find(None, None).await?;
             while let Some(doc) = cursor.try_next().await? {
@@ -130,9 +268,15 @@ impl<'a> Monitor<'a> {
     }
 
     /// Reorganize databases state
-    /// (full delete of orphaned collections and databases)
+    /// ( orphaned collections are handled according to `napalm_policy` )
     // *********************************************************************************************
-    async fn napalm(&self, client: &Client) -> Result<(), Box<dyn Error>> {
+    ///
+    /// Returns ( orphaned collections, archived collections ) - (database, collection).
+    ///
+    async fn napalm(
+        &self,
+        client: &Client,
+    ) -> Result<(Vec<(String, String)>, Vec<(String, String)>), Box<dyn Error>> {
         // Get the name of the technical database for a project.
         let db_green_tech: String = self.green_tech_name()?;
         let collection_models_name: &str = "monitor_models";
@@ -141,37 +285,229 @@ impl<'a> Monitor<'a> {
         let collection_models = green_tech_db.collection::<Document>(collection_models_name);
         let collection_dyn_fields =
             green_tech_db.collection::<Document>(collection_dyn_fields_type);
-        // Delete orphaned Collections.
-        let mut cursor = collection_models.find(None, None).await?;
+        // Get orphaned Collections (left without a model).
+        let mut orphaned_collections = Vec::<(String, String)>::new();
+        let mut cursor = collection_models.find(doc! {"status": false}, None).await?;
         while let Some(doc) = cursor.try_next().await? {
             let model_state: ModelState = from_document(doc)?;
-            if !model_state.status {
-                // Delete Collection (left without a model).
+            orphaned_collections.push((model_state.database, model_state.collection));
+        }
+        if orphaned_collections.is_empty() {
+            return Ok((orphaned_collections, Vec::new()));
+        }
+        if self.napalm_policy == NapalmPolicy::Refuse {
+            Err(Self::refuse_err_msg(&orphaned_collections))?
+        }
+        // Archived Collections - (database, collection).
+        let mut archived_collections = Vec::<(String, String)>::new();
+        let timestamp = Utc::now().format("%Y%m%d%H%M%S").to_string();
+        for (database, collection) in orphaned_collections.iter() {
+            let query: Document = doc! {
+                "database": database,
+                "collection": collection
+            };
+            if self.napalm_policy == NapalmPolicy::Archive {
+                // Rename Collection into the archive namespace.
+                let archive_name = format!("archive__{collection}__{timestamp}");
+                client
+                    .database("admin")
+                    .run_command(
+                        doc! {
+                            "renameCollection": format!("{database}.{collection}"),
+                            "to": format!("{database}.{archive_name}"),
+                        },
+                        None,
+                    )
+                    .await?;
+                // Keep the values of dynamic fields for the archived collection.
+                collection_dyn_fields
+                    .update_one(
+                        query.clone(),
+                        doc! {"$set": {"collection": &archive_name}},
+                        None,
+                    )
+                    .await?;
+                archived_collections.push((database.clone(), archive_name));
+            } else {
+                // Delete Collection.
                 client
-                    .database(&model_state.database)
-                    .collection::<Document>(&model_state.collection)
+                    .database(database)
+                    .collection::<Document>(collection)
                     .drop(None)
                     .await?;
-                // Delete a document with a record about the state of
-                // the model from the technical base.
-                let query: Document = doc! {
-                    "database": &model_state.database,
-                    "collection": &model_state.collection
-                };
-                collection_models.delete_one(query.clone(), None).await?;
-                collection_dyn_fields.delete_one(query, None).await?;
+                collection_dyn_fields
+                    .delete_one(query.clone(), None)
+                    .await?;
             }
+            // Delete a document with a record about the state of
+            // the model from the technical base.
+            collection_models.delete_one(query, None).await?;
         }
         //
-        Ok(())
+        Ok((orphaned_collections, archived_collections))
+    }
+
+    /// Error message of `NapalmPolicy::Refuse`.
+    // *********************************************************************************************
+    fn refuse_err_msg(orphaned_collections: &[(String, String)]) -> String {
+        format!(
+            "Method: `migrat()` => There are collections left without a model - {}. \
+            Register the models in `model_key_list` or set the `napalm_policy` \
+            parameter of Monitor to `NapalmPolicy::Archive` or `NapalmPolicy::Drop`.",
+            orphaned_collections
+                .iter()
+                .map(|(database, collection)| format!("`{database}.{collection}`"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+
+    /// Get a list of orphaned collections - (database, collection),
+    /// without changing the state of models.
+    /// Hint: Collections of the registered Models with `is_add_doc = false` are orphaned too.
+    // *********************************************************************************************
+    async fn orphaned_collections(
+        &self,
+        client: &Client,
+    ) -> Result<Vec<(String, String)>, Box<dyn Error>> {
+        // Collections of the registered Models - (database, collection).
+        let mut model_collections = Vec::<(String, String)>::new();
+        {
+            // Get metadata store.
+            let metadata = METADATA.lock().await;
+            for model_key in self.model_key_list.iter() {
+                // Get metadata of Model.
+                if let Some(meta) = metadata.get(model_key) {
+                    if meta.is_add_doc {
+                        model_collections
+                            .push((meta.database_name.clone(), meta.collection_name.clone()));
+                    }
+                } else {
+                    Err(format!(
+                        "Model key: `{model_key}` ; Method: `orphaned_collections()` => \
+                        Failed to get data from cache.",
+                    ))?
+                }
+            }
+        }
+        let db_green_tech: String = self.green_tech_name()?;
+        let collection_models = client
+            .database(&db_green_tech)
+            .collection::<Document>("monitor_models");
+        let mut orphaned_collections = Vec::<(String, String)>::new();
+        let mut cursor = collection_models.find(None, None).await?;
+        while let Some(doc) = cursor.try_next().await? {
+            let model_state: ModelState = from_document(doc)?;
+            let key = (model_state.database, model_state.collection);
+            if !model_collections.contains(&key) {
+                orphaned_collections.push(key);
+            }
+        }
+        Ok(orphaned_collections)
     }
 
     /// Migrating Models
     // *********************************************************************************************
     /// Check the changes in the models and (if necessary) apply to the database.
-    pub async fn migrat(&self, client: &Client) -> Result<(), Box<dyn Error>> {
+    /// Hint: Values of fields declared in the model parameter `renamed_fields`
+    /// are moved with `$rename`, instead of being replaced with default values.
+    /// When the field type changes, values are converted ( see `migration::convert` ),
+    /// values that cannot be converted are kept and listed in the report.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let report = monitor.migrat(&client).await?;
+    /// println!("{:?}", report.orphaned_collections);
+    /// ```
+    ///
+    /// Hint: The migration runs under a lease-based lock in the technical database
+    /// ( see `migration::lock` ), other instances of the application wait for it to complete.
+    ///
+    pub async fn migrat(&self, client: &Client) -> Result<MigrationReport, Box<dyn Error>> {
+        let db_green_tech: String = self.green_tech_name()?;
+        let db = client.database(&db_green_tech);
+        let lock = MigrationLock::acquire(&db, MIGRAT_LOCK, MIGRATION_LOCK_TTL).await?;
+        let result = self.migrat_ext(client, Some(&lock)).await;
+        lock.release().await?;
+        result
+    }
+
+    /// Migrating Models, if no other instance of the application is migrating
+    // *********************************************************************************************
+    /// Returns None ( the migration is skipped ) if the lock is held by another instance.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// match monitor.try_migrat(&client).await? {
+    ///     Some(report) => println!("{:?}", report.orphaned_collections),
+    ///     None => println!("Migration is performed by another instance."),
+    /// }
+    /// ```
+    ///
+    pub async fn try_migrat(
+        &self,
+        client: &Client,
+    ) -> Result<Option<MigrationReport>, Box<dyn Error>> {
+        let db_green_tech: String = self.green_tech_name()?;
+        let db = client.database(&db_green_tech);
+        let lock = match MigrationLock::try_acquire(&db, MIGRAT_LOCK, MIGRATION_LOCK_TTL).await? {
+            Some(lock) => lock,
+            None => return Ok(None),
+        };
+        let result = self.migrat_ext(client, Some(&lock)).await;
+        lock.release().await?;
+        result.map(Some)
+    }
+
+    /// Migration plan
+    // *********************************************************************************************
+    /// Get a report of the changes that `migrat()` would make, without changing the database.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let report = monitor.plan(&client).await?;
+    /// if !report.orphaned_collections.is_empty() {
+    ///     Err(format!("Collections to drop: {:?}", report.orphaned_collections))?
+    /// }
+    /// monitor.migrat(&client).await?;
+    /// ```
+    ///
+    pub async fn plan(&self, client: &Client) -> Result<MigrationReport, Box<dyn Error>> {
+        self.migrat_ext(client, None).await
+    }
+
+    /// Check the changes in the models and apply to the database
+    /// ( if the migration lock is passed, otherwise it is a dry run ).
+    // *********************************************************************************************
+    async fn migrat_ext(
+        &self,
+        client: &Client,
+        lock: Option<&MigrationLock>,
+    ) -> Result<MigrationReport, Box<dyn Error>> {
+        let is_dry_run = lock.is_none();
+        // With `NapalmPolicy::Refuse`, orphaned collections are detected
+        // before any changes, so the database is left untouched.
+        if !is_dry_run && self.napalm_policy == NapalmPolicy::Refuse {
+            let orphaned_collections = self.orphaned_collections(client).await?;
+            if !orphaned_collections.is_empty() {
+                Err(Self::refuse_err_msg(&orphaned_collections))?
+            }
+        }
         // Run refresh models state.
-        self.refresh(client).await?;
+        if !is_dry_run {
+            self.refresh(client).await?;
+        }
+        // Migration result.
+        let mut report = MigrationReport {
+            is_dry_run,
+            ..Default::default()
+        };
+        // Regular expressions for validation of service and database names.
+        let re_service_name = Regex::new(r"^[_a-zA-Z][_a-zA-Z\d]{1,30}$")?;
+        let re_database_name = Regex::new(r"^[_a-zA-Z][_a-zA-Z\d]{14,61}$")?;
         for model_key in self.model_key_list.iter() {
             // Get metadata of Model
             let meta = {
@@ -190,11 +526,15 @@ impl<'a> Monitor<'a> {
             if !meta.is_add_doc {
                 continue;
             }
+            // Changes in the collection of the current Model.
+            let mut model_report = ModelReport {
+                model_name: meta.model_name.clone(),
+                database: meta.database_name.clone(),
+                collection: meta.collection_name.clone(),
+                ..Default::default()
+            };
             // Service_name validation.
-            if !Regex::new(r"^[_a-zA-Z][_a-zA-Z\d]{1,30}$")
-                .unwrap()
-                .is_match(meta.service_name.as_str())
-            {
+            if !re_service_name.is_match(meta.service_name.as_str()) {
                 Err(format!(
                     "Model: `{}` > SERVICE_NAME => \
                         Valid characters: _ a-z A-Z 0-9 \
@@ -204,10 +544,7 @@ impl<'a> Monitor<'a> {
                 ))?;
             }
             // Database name validation.
-            if !Regex::new(r"^[_a-zA-Z][_a-zA-Z\d]{14,61}$")
-                .unwrap()
-                .is_match(meta.database_name.as_str())
-            {
+            if !re_database_name.is_match(meta.database_name.as_str()) {
                 Err(format!(
                     "Model: `{}` > DATABASE_NAME => \
                         Valid characters: _ a-z A-Z 0-9 \
@@ -240,7 +577,9 @@ impl<'a> Monitor<'a> {
                 .retain(|item, _| item != "hash" && !ignore_fields.contains(item));
             // Get a map of fields type from the technical database,
             // from the `monitor_models` collection for current Model.
-            let monitor_field_type_map: HashMap<String, String>;
+            let mut monitor_field_type_map: HashMap<String, String>;
+            // Renamed fields whose values have been moved - (old_name, new_name).
+            let mut applied_renames = Vec::<(String, String)>::new();
 
             // Check the field changes in the Model and (if required)
             // update documents in the current Collection.
@@ -259,7 +598,7 @@ impl<'a> Monitor<'a> {
             if let Some(model) = model {
                 // Get a list of fields from the technical database,
                 // from the `monitor_models` collection for current Model.
-                let monitor_models_fields_name: Vec<String> = {
+                let mut monitor_models_fields_name: Vec<String> = {
                     let fields: Vec<Bson> = model.get_array("fields")?.to_vec();
                     fields
                         .into_iter()
@@ -276,6 +615,73 @@ impl<'a> Monitor<'a> {
                         .map(|item| (item.0.clone(), item.1.as_str().unwrap().to_string()))
                         .collect()
                 };
+                // Move the values of renamed fields ( model parameter `renamed_fields` ).
+                let mut rename_doc = Document::new();
+                for (new_name, old_name) in meta.renamed_fields.iter() {
+                    if !trunc_fields_name_list.contains(&new_name) {
+                        Err(format!(
+                            "Model: `{}` ; Method: `migrat()` => \
+                            Parameter `renamed_fields` - The model has no field `{}`.",
+                            meta.model_name, new_name
+                        ))?
+                    }
+                    if monitor_models_fields_name.contains(old_name)
+                        && !monitor_models_fields_name.contains(new_name)
+                    {
+                        rename_doc.insert(old_name, new_name);
+                        // Update the state of the model fields.
+                        for field_name in monitor_models_fields_name.iter_mut() {
+                            if field_name == old_name {
+                                *field_name = new_name.clone();
+                            }
+                        }
+                        if let Some(field_type) = monitor_field_type_map.remove(old_name) {
+                            monitor_field_type_map.insert(new_name.clone(), field_type);
+                        }
+                        applied_renames.push((old_name.clone(), new_name.clone()));
+                    }
+                }
+                if !rename_doc.is_empty() {
+                    let collection = client
+                        .database(&meta.database_name)
+                        .collection::<Document>(&meta.collection_name);
+                    if !is_dry_run {
+                        model_report.renamed_docs = collection
+                            .update_many(doc! {}, doc! {"$rename": rename_doc}, None)
+                            .await?
+                            .modified_count;
+                    } else {
+                        let filter = rename_doc
+                            .keys()
+                            .map(|old_name| doc! {old_name: {"$exists": true}})
+                            .collect::<Vec<Document>>();
+                        model_report.renamed_docs = collection
+                            .count_documents(doc! {"$or": filter}, None)
+                            .await?;
+                    }
+                    model_report.renamed_fields = applied_renames.clone();
+                }
+                // Added, removed and retyped fields.
+                for field in trunc_fields_name_list.iter() {
+                    if let Some(old_type) = monitor_field_type_map.get(*field) {
+                        let new_type = trunc_field_type_map.get(*field).unwrap();
+                        if monitor_models_fields_name.contains(field) && old_type != new_type {
+                            model_report.retyped_fields.push((
+                                field.to_string(),
+                                old_type.clone(),
+                                new_type.clone(),
+                            ));
+                        }
+                    }
+                    if !monitor_models_fields_name.contains(field) {
+                        model_report.added_fields.push(field.to_string());
+                    }
+                }
+                for field in monitor_models_fields_name.iter() {
+                    if !trunc_fields_name_list.contains(&field) {
+                        model_report.removed_fields.push(field.clone());
+                    }
+                }
                 // Check if the set of fields in the collection of
                 // the current Model needs to be updated.
                 let mut changed_fields: Vec<&str> = Vec::new();
@@ -288,283 +694,195 @@ impl<'a> Monitor<'a> {
                     }
                 }
                 // Start (if necessary) updating the set of fields in the current collection.
+                // Hint: Only the changed fields are rewritten, in batches ( see `migration::rewrite` ).
                 if !changed_fields.is_empty() {
+                    // Registered converters and default values of the changed fields.
+                    let mut converters = HashMap::new();
+                    let mut default_values = HashMap::<&str, Bson>::new();
+                    for field_name in changed_fields.iter() {
+                        converters.insert(
+                            field_name.to_string(),
+                            get_field_converter(model_key, field_name).await,
+                        );
+                        default_values.insert(
+                            field_name,
+                            Self::default_field_value(
+                                field_name,
+                                &field_type_map[*field_name],
+                                &default_value_map[*field_name],
+                                &meta,
+                            )?,
+                        );
+                    }
                     // Get the database and collection of the current Model.
                     let db: Database = client.database(&meta.database_name);
                     let collection = db.collection::<Document>(&meta.collection_name);
-                    // Get cursor to all documents of the current Model.
-                    let mut cursor = collection.find(None, None).await?;
-                    // Iterate through all documents in a current (model) collection.
-                    while let Some(doc_from_db) = cursor.try_next().await? {
-                        // Create temporary blank document.
-                        let mut tmp_doc = Document::new();
-                        // Loop over all fields of the model.
-                        for (field_name, field_type) in field_type_map.iter() {
-                            if field_name == "hash" || ignore_fields.contains(field_name) {
-                                continue;
-                            }
-                            // Insert the reserved fields.
-                            if field_name == "created_at" || field_name == "updated_at" {
-                                if doc_from_db.contains_key(field_name) {
-                                    let value_from_db: Option<&Bson> = doc_from_db.get(field_name);
-                                    if let Some(value_from_db) = value_from_db {
-                                        tmp_doc.insert(field_name.to_string(), value_from_db);
-                                    } else {
-                                        Err(format!(
-                                            "Service: `{}` > Model: `{}` ; \
-                                                Method: `migrat()` => \
-                                                Cannot get field value from database for \
-                                                field `{}`.",
-                                            meta.service_name, meta.model_name, field_name
-                                        ))?
-                                    }
-                                } else {
-                                    Err(format!(
-                                        "Service: `{}` > Model: `{}` ; Method: `migrat()` => \
-                                            Key `{}` was not found in the document from \
-                                            the database.",
-                                        meta.service_name, meta.model_name, field_name
-                                    ))?
-                                }
-                                //
-                                continue;
-                            }
-                            // If the field exists, get its value.
-                            if !changed_fields.contains(&field_name.as_str()) {
-                                let value_from_db: Option<&Bson> = doc_from_db.get(field_name);
-                                if let Some(value_from_db) = value_from_db {
-                                    tmp_doc.insert(field_name.to_string(), value_from_db);
-                                } else {
-                                    Err(format!(
-                                        "Service: `{}` > Model: `{}` > Field: `{}` ; \
-                                            Method: `migrat()` => \
-                                            Can't get field value from database.",
-                                        meta.service_name, meta.model_name, field_name
-                                    ))?;
-                                }
-                            } else {
-                                // If no field exists, get default value.
-                                let default_value = default_value_map.get(field_name).unwrap();
-                                tmp_doc.insert(
-                                    field_name.clone(),
-                                    match field_type.as_str() {
-                                         "ColorField" | "EmailField" | "PasswordField" | "PhoneField"| "TextField" 
-                                        | "URLField" | "IPField"  |"ChoiceTextField" | "SlugField" => {
-                                            if !default_value.is_null() {
-                                                Bson::String(default_value.as_str().unwrap().to_string())
-                                            } else {
-                                                Bson::Null
-                                            }
-                                        }
-                                        "DateField" => {
-                                           if !default_value.is_null() {
-                                                let val = format!("{}T00:00",default_value.as_str().unwrap());
-                                                if let Ok(ndt) = chrono::NaiveDateTime::parse_from_str( &val, "%Y-%m-%dT%H:%M")
-                                                {
-                                                    let dt = chrono::DateTime::<Utc>::from_naive_utc_and_offset(ndt,Utc);
-                                                    Bson::DateTime(dt.into())
-                                                } else {
-                                                    Err(format!("Service: `{}` > Model: `{}` ; \
-                                                        Method: `migrat()` => \
-                                                        Incorrect date format. \
-                                                        Example: 1970-02-28",
-                                                        meta.service_name, meta.model_name
-                                                    ))?
-                                                }
-                                            } else {
-                                                Bson::Null
-                                            }
-                                        }
-                                        "DateTimeField" | "HiddenDateTimeField" => {
-                                            if !default_value.is_null() {
-                                                let val = default_value.as_str().unwrap();
-                                                if let Ok(ndt) = chrono::NaiveDateTime::parse_from_str( val, "%Y-%m-%dT%H:%M")
-                                                {
-                                                    let dt = chrono::DateTime::<Utc>::from_naive_utc_and_offset(ndt,Utc);
-                                                    Bson::DateTime(dt.into())
-                                                } else {
-                                                    Err(format!("Service: `{}` > Model: `{}` ; \
-                                                        Method: `migrat()` => \
-                                                        Incorrect date and time format. \
-                                                        Example: 1970-02-28T00:00",
-                                                        meta.service_name, meta.model_name
-                                                    ))?
-                                                }
-                                            } else {
-                                                Bson::Null
-                                            }
-                                        }
-                                        "I32Field" | "ChoiceI32Field" => {
-                                            if !default_value.is_null() {
-                                                Bson::Int32(
-                                                    i32::try_from(default_value.as_i64().unwrap())?
-                                                )
-                                            } else {
-                                                Bson::Null
-                                            }
-                                        }
-                                        "U32Field" | "ChoiceU32Field" | "I64Field" | "ChoiceI64Field" => {
-                                            if !default_value.is_null() {
-                                                Bson::Int64(
-                                                    default_value.as_i64().unwrap()
-                                                )
-                                            } else {
-                                                Bson::Null
-                                            }
-                                        }
-                                        "F64Field" | "ChoiceF64Field" => {
-                                            if !default_value.is_null() {
-                                                Bson::Double(
-                                                   default_value.as_f64().unwrap()
-                                                )
+                    // The checkpoint is valid only for the same changes of fields.
+                    let changes = changed_fields
+                        .iter()
+                        .map(|field_name| {
+                            format!(
+                                "{field_name}:{}>{}",
+                                monitor_field_type_map
+                                    .get(*field_name)
+                                    .map_or("", String::as_str),
+                                field_type_map[*field_name]
+                            )
+                        })
+                        .collect::<Vec<String>>()
+                        .join(",");
+                    let checkpoint = Checkpoint::new(
+                        &client.database(&db_green_tech),
+                        &meta.database_name,
+                        &meta.collection_name,
+                        changes,
+                    );
+                    let mut progress = RewriteProgress {
+                        database: meta.database_name.clone(),
+                        collection: meta.collection_name.clone(),
+                        processed: 0,
+                        total: collection.estimated_document_count(None).await?,
+                        is_resumed: false,
+                    };
+                    // Continue the interrupted rewrite from the last processed `_id`.
+                    let mut filter: Option<Document> = None;
+                    if !is_dry_run {
+                        if let Some((last_id, processed)) = checkpoint.load().await? {
+                            filter = Some(doc! {"_id": {"$gt": last_id}});
+                            progress.processed = processed;
+                            progress.is_resumed = true;
+                        }
+                    }
+                    // Names of the changed fields in the documents - <field_name, name in the document>.
+                    // Hint: In the migration plan, renamed fields still have the old names.
+                    let mut source_names = HashMap::<&str, &str>::new();
+                    for field_name in changed_fields.iter() {
+                        let source_name = applied_renames
+                            .iter()
+                            .find(|(_, new_name)| is_dry_run && new_name == field_name)
+                            .map_or(*field_name, |(old_name, _)| old_name.as_str());
+                        source_names.insert(field_name, source_name);
+                    }
+                    // Get cursor to the documents of the current Model ( in ascending order of `_id` ),
+                    // only the values of the changed fields are needed.
+                    let mut projection = doc! {"_id": 1};
+                    for source_name in source_names.values() {
+                        projection.insert(*source_name, 1);
+                    }
+                    let options = FindOptions::builder()
+                        .sort(doc! {"_id": 1})
+                        .projection(projection)
+                        .batch_size(REWRITE_BATCH_SIZE)
+                        .build();
+                    let mut cursor = collection.find(filter, options).await?;
+                    // Batch of updates - (_id, fields to set).
+                    let mut batch = Vec::<(Bson, Document)>::new();
+                    loop {
+                        let doc_from_db = cursor.try_next().await?;
+                        if let Some(doc_from_db) = doc_from_db.as_ref() {
+                            // Create temporary blank document.
+                            let mut tmp_doc = Document::new();
+                            for field_name in changed_fields.iter() {
+                                let field_type = &field_type_map[*field_name];
+                                // If the field type has changed, convert the value.
+                                if let Some(old_type) = monitor_field_type_map.get(*field_name) {
+                                    let value_from_db = doc_from_db
+                                        .get(source_names[*field_name])
+                                        .unwrap_or(&Bson::Null);
+                                    if value_from_db != &Bson::Null {
+                                        let result =
+                                            if let Some(converter) = &converters[*field_name] {
+                                                converter(value_from_db, old_type, field_type)
                                             } else {
-                                                Bson::Null
-                                            }
-                                        }
-                                        "BoolField" => {
-                                            if !default_value.is_null() {
-                                                Bson::Boolean(
-                                                    default_value.as_bool().unwrap()
+                                                convert_value(
+                                                    value_from_db,
+                                                    field_type,
+                                                    field_name,
+                                                    &meta,
                                                 )
-                                            } else {
-                                                Bson::Boolean(false)
-                                            }
-                                        }
-                                        "FileField" => {
-                                            if !default_value.is_null() {
-                                                let mut file_data = serde_json::from_value::<FileData>(default_value.clone())?;
-                                                // Define flags to check.
-                                                if file_data.path.is_empty() || file_data.url.is_empty() {
-                                                    Err(format!("Model: `{}` > Field: `{}` ; Method: \
-                                                        `migrat()` => Check the `path` and `url` \
-                                                        attributes in the `default` field parameter.",
-                                                        meta.model_name, field_name)
-                                                    )?
-                                                }
-                                                // Create path for validation of file.
-                                                let path: String = file_data.path.clone();
-                                                let f_path = Path::new(path.as_str());
-                                                if !f_path.is_file() {
-                                                    Err(format!("Model: `{}` > Field: `{}` ; \
-                                                    Method: `migrat()` => File is missing - {}",
-                                                        meta.model_name, field_name, path)
-                                                    )?
-                                                }
-                                                // Get file metadata.
-                                                let metadata = f_path.metadata()?;
-                                                // Get file size in bytes.
-                                                file_data.size = metadata.len() as f64;
-                                                // Get file name.
-                                                file_data.name = f_path.file_name().unwrap().to_str().unwrap().to_string();
-                                                // Create doc.
-                                                let result = to_document(&file_data)?;
-                                                Bson::Document(result)
-                                            } else {
-                                                Bson::Null
-                                            }
-                                        }
-                                        "ImageField" => {
-                                            if !default_value.is_null() {
-                                                let mut file_data = serde_json::from_value::<ImageData>(default_value.clone())?;
-                                                // Define flags to check.
-                                                if file_data.path.is_empty() || file_data.url.is_empty() {
-                                                    Err(format!("Model: `{}` > Field: `{}` ; Method: \
-                                                        `migrat()` => Check the `path` and `url` \
-                                                        attributes in the `default` field parameter.",
-                                                        meta.model_name, field_name
-                                                    ))?
-                                                }
-                                                // Create path for validation of file.
-                                                let path: String = file_data.path.clone();
-                                                let f_path = Path::new(path.as_str());
-                                                if !f_path.is_file() {
-                                                    Err(format!("Model: `{}` > Field: `{}` ; Method: \
-                                                            `migrat()` => Image is missing - {}",
-                                                        meta.model_name, field_name, path
-                                                    ))?
-                                                }
-                                                // Get file metadata.
-                                                let metadata = f_path.metadata()?;
-                                                // Get file size in bytes.
-                                                file_data.size = metadata.len() as f64;
-                                                // Get file name.
-                                                file_data.name = f_path.file_name().unwrap().to_str().unwrap().to_string();
-                                                // Get image width and height.
-                                                let dimensions = image::image_dimensions(path)?;
-                                                file_data.width = dimensions.0 as f64;
-                                                file_data.height = dimensions.1 as f64;
-                                                // Create doc.
-                                                let result = to_document(&file_data)?;
-                                                Bson::Document(result)
-                                            } else {
-                                                Bson::Null
-                                            }
-                                        }
-                                        "ChoiceTextMultField" => {
-                                            if !default_value.is_null() {
-                                                let val = serde_json::from_value::<Vec<String>>(default_value.clone())?
-                                                    .iter().map(|item| Bson::String(item.clone()))
-                                                    .collect::<Vec<Bson>>();
-                                                Bson::Array(val)
-                                            } else {
-                                                Bson::Null
+                                            };
+                                        match result {
+                                            Ok(value) => {
+                                                tmp_doc.insert(field_name.to_string(), value);
+                                                continue;
                                             }
-                                        }
-                                        "ChoiceI32MultField" => {
-                                            if !default_value.is_null() {
-                                                let val = serde_json::from_value::<Vec<i32>>(default_value.clone())?
-                                                    .iter().map(|item| Bson::Int32(*item))
-                                                    .collect::<Vec<Bson>>();
-                                                Bson::Array(val)
-                                            } else {
-                                                Bson::Null
+                                            Err(error) => {
+                                                report.unconvertible.push(UnconvertibleValue {
+                                                    database: meta.database_name.clone(),
+                                                    collection: meta.collection_name.clone(),
+                                                    field_name: field_name.to_string(),
+                                                    doc_id: doc_from_db
+                                                        .get("_id")
+                                                        .cloned()
+                                                        .unwrap_or(Bson::Null),
+                                                    value: value_from_db.clone(),
+                                                    old_type: old_type.clone(),
+                                                    new_type: field_type.clone(),
+                                                    error,
+                                                });
+                                                // Keep the original value.
+                                                continue;
                                             }
                                         }
-                                         "ChoiceU32MultField" | "ChoiceI64MultField"  => {
-                                            if !default_value.is_null() {
-                                                let val = serde_json::from_value::<Vec<i64>>(default_value.clone())?
-                                                    .iter().map(|item| mongodb::bson::Bson::Int64(*item))
-                                                    .collect::<Vec<Bson>>();
-                                                Bson::Array(val)
-                                            } else {
-                                                Bson::Null
-                                            }
-                                        }
-                                        "ChoiceF64MultField" => {
-                                            if !default_value.is_null() {
-                                                let val = serde_json::from_value::<Vec<f64>>(default_value.clone())?
-                                                    .iter().map(|item| Bson::Double(*item))
-                                                    .collect::<Vec<Bson>>();
-                                                Bson::Array(val)
-                                            } else {
-                                                Bson::Null
-                                            }
-                                        }
-                                        "ChoiceTextDynField" | "ChoiceTextMultDynField" | "ChoiceI32DynField"
-                                        | "ChoiceI32MultDynField" | "ChoiceU32DynField" | "ChoiceU32MultDynField"
-                                        | "ChoiceI64DynField" | "ChoiceI64MultDynField" | "ChoiceF64DynField"
-                                        | "ChoiceF64MultDynField" => {
-                                            Bson::Null
-                                        }
-                                        _ => {
-                                            Err(format!("Service: `{}` > Model: `{}` ; \
-                                                    Method: `migrat()` => Invalid Field type.",
-                                                meta.service_name, meta.model_name
-                                            ))?
-                                        }
-                                    },
-                                );
+                                    }
+                                }
+                                // If no field exists, get default value.
+                                // Hint: Automatic UUIDs are unique for each document.
+                                let value = if field_type == "UUIDField"
+                                    && default_value_map[*field_name].is_null()
+                                {
+                                    Self::default_field_value(
+                                        field_name,
+                                        field_type,
+                                        &default_value_map[*field_name],
+                                        &meta,
+                                    )?
+                                } else {
+                                    default_values[*field_name].clone()
+                                };
+                                tmp_doc.insert(field_name.to_string(), value);
                             }
+                            let doc_id = doc_from_db.get("_id").cloned().unwrap_or(Bson::Null);
+                            batch.push((doc_id, tmp_doc));
+                        }
+                        // Save the batch of updated documents and the checkpoint.
+                        if batch.len() == REWRITE_BATCH_SIZE as usize
+                            || (doc_from_db.is_none() && !batch.is_empty())
+                        {
+                            progress.processed += batch.len() as u64;
+                            if let Some(lock) = lock {
+                                // Another instance may have taken over the lease,
+                                // the rewrite continues from the checkpoint on the next run.
+                                if lock.is_lost() {
+                                    Err(format!(
+                                        "Migration: `{}.{}` => The migration lock was lost, \
+                                        the rewrite is stopped after {} of {} documents.",
+                                        meta.database_name,
+                                        meta.collection_name,
+                                        progress.processed - batch.len() as u64,
+                                        progress.total
+                                    ))?
+                                }
+                                write_batch(&db, &meta.collection_name, &batch).await?;
+                                checkpoint
+                                    .save(&batch[batch.len() - 1].0, progress.processed)
+                                    .await?;
+                                report_progress(&progress).await;
+                            }
+                            batch.clear();
+                        }
+                        if doc_from_db.is_none() {
+                            break;
                         }
-                        // Save updated document.
-                        let query = doc! {"_id": doc_from_db.get_object_id("_id")?};
-                        collection
-                            .update_one(query, doc! {"$set": tmp_doc}, None)
-                            .await?;
                     }
+                    if !is_dry_run {
+                        checkpoint.clear().await?;
+                    }
+                    model_report.affected_docs = progress.processed;
                 }
             } else {
                 monitor_field_type_map = HashMap::new();
+                model_report.is_new_model = true;
             }
 
             // Create a new database (if doesn't exist) and add new collection.
@@ -578,9 +896,24 @@ impl<'a> Monitor<'a> {
                     .await?
                     .contains(&meta.collection_name)
             {
-                db.create_collection(&meta.collection_name, None).await?;
+                model_report.is_new_collection = true;
+                if !is_dry_run {
+                    db.create_collection(&meta.collection_name, None).await?;
+                }
             }
 
+            // Create the declared indexes and drop the indexes that are no longer declared.
+            // -------------------------------------------------------------------------------------
+            let collection = db.collection::<Document>(&meta.collection_name);
+            let (created_indexes, dropped_indexes) =
+                sync_indexes(&collection, &meta, is_dry_run).await?;
+            model_report.created_indexes = created_indexes;
+            model_report.dropped_indexes = dropped_indexes;
+
+            // Apply the `$jsonSchema` validator of the Model.
+            // -------------------------------------------------------------------------------------
+            model_report.is_validator_changed = sync_validator(&db, &meta, is_dry_run).await?;
+
             // Get the technical database `db_green_tech` for the current model.
             // -------------------------------------------------------------------------------------
             let db: Database = client.database(&db_green_tech);
@@ -588,7 +921,9 @@ impl<'a> Monitor<'a> {
             // Update the state of models for `models::Monitor`.
             // -------------------------------------------------------------------------------------
             // Check if there is a technical database of the project, if not, causes panic.
-            if !database_names.contains(&db_green_tech)
+            if is_dry_run {
+                // The state of models is not changed.
+            } else if !database_names.contains(&db_green_tech)
                 || !db
                     .list_collection_names(None)
                     .await?
@@ -625,11 +960,12 @@ impl<'a> Monitor<'a> {
             // Document management to support model fields with dynamic fields type.
             // -------------------------------------------------------------------------------------
             // Check if there is a technical database of the project, if not, causes panic.
-            if !database_names.contains(&db_green_tech)
-                || !db
-                    .list_collection_names(None)
-                    .await?
-                    .contains(&"dynamic_fields".to_owned())
+            if !is_dry_run
+                && (!database_names.contains(&db_green_tech)
+                    || !db
+                        .list_collection_names(None)
+                        .await?
+                        .contains(&"dynamic_fields".to_owned()))
             {
                 Err("In the `refresh()` method, \
                         no technical database has been created for the project.")?
@@ -658,12 +994,20 @@ impl<'a> Monitor<'a> {
                 }
                 // Insert new document.
                 new_doc.insert("fields".to_string(), fields_doc);
-                collection.insert_one(new_doc, None).await?;
+                if !is_dry_run {
+                    collection.insert_one(new_doc, None).await?;
+                }
             } else {
                 // Get an existing document.
                 let mut exist_doc = collection.find_one(filter.clone(), None).await?.unwrap();
                 // Get a document with `dynamic_fields` fields.
                 let fields_doc = exist_doc.get_document_mut("fields")?;
+                // Move the values of renamed fields.
+                for (old_name, new_name) in applied_renames.iter() {
+                    if let Some(value) = fields_doc.remove(old_name) {
+                        fields_doc.insert(new_name, value);
+                    }
+                }
                 // Get a list of fields from the technical database,
                 // from the `dynamic_fields` collection for current Model.
                 let dyn_fields_from_db: Vec<String> =
@@ -676,12 +1020,14 @@ impl<'a> Monitor<'a> {
                         dyn_fields_from_model.push(field_name.clone());
                         // If the new field or fields type do not match,
                         // initialize with an empty array.
-                        if !dyn_fields_from_db.contains(&field_name)
-                            || (field_type
-                                != *monitor_field_type_map
-                                    .get(field_name.as_str())
-                                    .unwrap_or(&String::new()))
-                        {
+                        let is_retyped = field_type
+                            != *monitor_field_type_map
+                                .get(field_name.as_str())
+                                .unwrap_or(&String::new());
+                        if dyn_fields_from_db.contains(&field_name) && is_retyped {
+                            model_report.reset_dyn_fields.push(field_name.clone());
+                        }
+                        if !dyn_fields_from_db.contains(&field_name) || is_retyped {
                             fields_doc.insert(field_name, Bson::Array(Vec::new()));
                         }
                     }
@@ -693,14 +1039,452 @@ impl<'a> Monitor<'a> {
                     }
                 }
                 // Full update existing document.
-                collection
-                    .update_one(filter, doc! {"$set":exist_doc}, None)
-                    .await?;
+                if !is_dry_run {
+                    collection
+                        .update_one(filter, doc! {"$set":exist_doc}, None)
+                        .await?;
+                }
             }
+            report.models.push(model_report);
         }
         // Run reorganize databases state.
-        self.napalm(client).await?;
+        if !is_dry_run {
+            let (orphaned_collections, archived_collections) = self.napalm(client).await?;
+            report.orphaned_collections = orphaned_collections;
+            report.archived_collections = archived_collections;
+        } else {
+            report.orphaned_collections = self.orphaned_collections(client).await?;
+        }
         //
+        Ok(report)
+    }
+
+    /// Collect orphaned media files
+    /// ( files in the dated `YYYY/MM/DD-barrel` and content-addressed `sha256` directories
+    /// that are not referenced by any document ).
+    // *********************************************************************************************
+    ///
+    /// Scans `media_root`/`target_dir` of every FileField and ImageField of registered Models
+    /// that use the local file system ( `storage` parameter is empty ).
+    /// Files that are newer than `min_age` are skipped - they can belong to a document
+    /// that is being saved right now ( default - `MEDIA_GC_MIN_AGE` ).
+    /// Hint: If `is_dry_run` is true, orphaned files are only reported.
+    ///
+    /// WARNING: Only documents of the Models from `model_key_list` are checked -
+    /// if a directory is shared with a Model that is missing from the list,
+    /// the files of that Model are deleted as orphaned.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let report = monitor
+    ///     .collect_media_garbage(&client, MEDIA_GC_MIN_AGE, true)
+    ///     .await?;
+    /// println!("{:?}", report.orphans);
+    /// ```
+    ///
+    pub async fn collect_media_garbage(
+        &self,
+        client: &Client,
+        min_age: Duration,
+        is_dry_run: bool,
+    ) -> Result<MediaGcReport, Box<dyn Error>> {
+        let re_media_path =
+            Regex::new(r"(?:/|\\)(?:\d{4}(?:/|\\)\d{2}(?:/|\\)\d{2}\-barrel|sha256)(?:/|\\)")?;
+        let path_keys = ["path", "path_xs", "path_sm", "path_md", "path_lg"];
+        // Directories for scanning.
+        let mut scan_dirs = Vec::<PathBuf>::new();
+        // Paths to files that are referenced by documents.
+        let mut referenced_paths = HashSet::<PathBuf>::new();
+        // Databases with reference counters of content-addressed files.
+        let mut database_names = Vec::<String>::new();
+        for model_key in self.model_key_list.iter() {
+            // Get metadata of Model
+            let meta = {
+                // Get metadata store.
+                let metadata = METADATA.lock().await;
+                // Get metadata of Model.
+                if let Some(meta) = metadata.get(model_key) {
+                    meta.clone()
+                } else {
+                    Err(format!(
+                        "Model key: `{model_key}` ; Method: `collect_media_garbage()` => \
+                    Failed to get data from cache.",
+                    ))?
+                }
+            };
+            // Get a list of file fields that use the local file system.
+            let media_fields = meta
+                .field_type_map
+                .iter()
+                .filter(|(field_name, field_type)| {
+                    (*field_type == "FileField" || *field_type == "ImageField")
+                        && !meta.ignore_fields.contains(field_name)
+                        && meta.model_json[field_name.as_str()]["storage"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .is_empty()
+                })
+                .map(|(field_name, _)| field_name.clone())
+                .collect::<Vec<String>>();
+            if media_fields.is_empty() {
+                continue;
+            }
+            if !database_names.contains(&meta.database_name) {
+                database_names.push(meta.database_name.clone());
+            }
+            let mut projection = doc! {};
+            for field_name in media_fields.iter() {
+                let field = &meta.model_json[field_name.as_str()];
+                let scan_dir = Path::new(field["media_root"].as_str().unwrap())
+                    .join(field["target_dir"].as_str().unwrap());
+                if !scan_dirs.contains(&scan_dir) {
+                    scan_dirs.push(scan_dir);
+                }
+                projection.insert(field_name, 1);
+            }
+            // Get paths to files from documents.
+            let coll = client
+                .database(&meta.database_name)
+                .collection::<Document>(&meta.collection_name);
+            let options = FindOptions::builder().projection(projection).build();
+            let mut cursor = coll.find(None, options).await?;
+            while let Some(doc) = cursor.try_next().await? {
+                for field_name in media_fields.iter() {
+                    if let Ok(info_file) = doc.get_document(field_name) {
+                        for key in path_keys {
+                            if let Ok(path) = info_file.get_str(key) {
+                                if !path.is_empty() {
+                                    referenced_paths.insert(Self::normalize_path(Path::new(path)));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        // Scan directories and collect orphaned files.
+        let mut report = MediaGcReport {
+            is_dry_run,
+            ..Default::default()
+        };
+        for scan_dir in scan_dirs.iter() {
+            let mut file_list = Vec::<PathBuf>::new();
+            Self::media_files(scan_dir, &mut file_list)?;
+            for file_path in file_list {
+                if !re_media_path.is_match(file_path.to_str().unwrap_or_default()) {
+                    continue;
+                }
+                report.scanned_count += 1;
+                if referenced_paths.contains(&Self::normalize_path(&file_path)) {
+                    continue;
+                }
+                let metadata = file_path.metadata()?;
+                // Modification time in the future - the file is new.
+                let age = metadata.modified()?.elapsed().unwrap_or_default();
+                if age < min_age {
+                    report.recent_count += 1;
+                    continue;
+                }
+                report.orphans_size += metadata.len();
+                report
+                    .orphans
+                    .push(file_path.to_string_lossy().into_owned());
+                if !is_dry_run {
+                    fs::remove_file(&file_path)?;
+                    // Remove the reference counter of the content-addressed file.
+                    let path = file_path.to_string_lossy();
+                    for database_name in database_names.iter() {
+                        client
+                            .database(database_name)
+                            .collection::<Document>(MEDIA_REFS)
+                            .delete_many(doc! {"data.path": path.as_ref()}, None)
+                            .await?;
+                    }
+                    // Remove empty directories.
+                    let mut dir_path = file_path.parent();
+                    while let Some(dir) = dir_path {
+                        if dir == scan_dir.as_path() || dir.read_dir()?.next().is_some() {
+                            break;
+                        }
+                        fs::remove_dir(dir)?;
+                        dir_path = dir.parent();
+                    }
+                }
+            }
+        }
+        //
+        Ok(report)
+    }
+
+    /// Get the default value of the field for migration.
+    // *********************************************************************************************
+    fn default_field_value(
+        field_name: &str,
+        field_type: &str,
+        default_value: &serde_json::Value,
+        meta: &Meta,
+    ) -> Result<Bson, Box<dyn Error>> {
+        let value = match field_type {
+            "ColorField" | "EmailField" | "PasswordField" | "PhoneField" | "TextField"
+            | "URLField" | "IPField" | "ChoiceTextField" | "ChoiceEnumField" | "SlugField"
+            | "TimeField" => {
+                if !default_value.is_null() {
+                    Bson::String(default_value.as_str().unwrap().to_string())
+                } else {
+                    Bson::Null
+                }
+            }
+            "DateField" => {
+                if !default_value.is_null() {
+                    let val = format!("{}T00:00", default_value.as_str().unwrap());
+                    if let Ok(ndt) = chrono::NaiveDateTime::parse_from_str(&val, "%Y-%m-%dT%H:%M") {
+                        let dt = chrono::DateTime::<Utc>::from_naive_utc_and_offset(ndt, Utc);
+                        Bson::DateTime(dt.into())
+                    } else {
+                        Err(format!(
+                            "Service: `{}` > Model: `{}` ; \
+                            Method: `migrat()` => \
+                            Incorrect date format. \
+                            Example: 1970-02-28",
+                            meta.service_name, meta.model_name
+                        ))?
+                    }
+                } else {
+                    Bson::Null
+                }
+            }
+            "DateTimeField" | "HiddenDateTimeField" => {
+                if !default_value.is_null() {
+                    let val = default_value.as_str().unwrap();
+                    if let Ok(ndt) = chrono::NaiveDateTime::parse_from_str(val, "%Y-%m-%dT%H:%M") {
+                        let dt = chrono::DateTime::<Utc>::from_naive_utc_and_offset(ndt, Utc);
+                        Bson::DateTime(dt.into())
+                    } else {
+                        Err(format!(
+                            "Service: `{}` > Model: `{}` ; \
+                            Method: `migrat()` => \
+                            Incorrect date and time format. \
+                            Example: 1970-02-28T00:00",
+                            meta.service_name, meta.model_name
+                        ))?
+                    }
+                } else {
+                    Bson::Null
+                }
+            }
+            "UUIDField" => {
+                let is_auto = meta.model_json[field_name]["is_auto"]
+                    .as_bool()
+                    .unwrap_or_default();
+                let uuid = if !default_value.is_null() {
+                    Some(Uuid::parse_str(default_value.as_str().unwrap())?)
+                } else if is_auto {
+                    Some(Uuid::new_v4())
+                } else {
+                    None
+                };
+                if let Some(uuid) = uuid {
+                    Bson::Binary(Binary {
+                        subtype: BinarySubtype::Uuid,
+                        bytes: uuid.as_bytes().to_vec(),
+                    })
+                } else {
+                    Bson::Null
+                }
+            }
+            "JsonField" => {
+                if !default_value.is_null() {
+                    to_bson(default_value)?
+                } else {
+                    Bson::Null
+                }
+            }
+            "I32Field" | "ChoiceI32Field" => {
+                if !default_value.is_null() {
+                    Bson::Int32(i32::try_from(default_value.as_i64().unwrap())?)
+                } else {
+                    Bson::Null
+                }
+            }
+            "U32Field" | "ChoiceU32Field" | "I64Field" | "ChoiceI64Field" => {
+                if !default_value.is_null() {
+                    Bson::Int64(default_value.as_i64().unwrap())
+                } else {
+                    Bson::Null
+                }
+            }
+            "F64Field" | "ChoiceF64Field" => {
+                if !default_value.is_null() {
+                    Bson::Double(default_value.as_f64().unwrap())
+                } else {
+                    Bson::Null
+                }
+            }
+            "BoolField" => {
+                if !default_value.is_null() {
+                    Bson::Boolean(default_value.as_bool().unwrap())
+                } else {
+                    Bson::Boolean(false)
+                }
+            }
+            "FileField" => {
+                if !default_value.is_null() {
+                    let mut file_data = serde_json::from_value::<FileData>(default_value.clone())?;
+                    // Define flags to check.
+                    if file_data.path.is_empty() || file_data.url.is_empty() {
+                        Err(format!(
+                            "Model: `{}` > Field: `{}` ; Method: \
+                            `migrat()` => Check the `path` and `url` \
+                            attributes in the `default` field parameter.",
+                            meta.model_name, field_name
+                        ))?
+                    }
+                    // Create path for validation of file.
+                    let path: String = file_data.path.clone();
+                    let f_path = Path::new(path.as_str());
+                    if !f_path.is_file() {
+                        Err(format!(
+                            "Model: `{}` > Field: `{}` ; \
+                        Method: `migrat()` => File is missing - {}",
+                            meta.model_name, field_name, path
+                        ))?
+                    }
+                    // Get file metadata.
+                    let metadata = f_path.metadata()?;
+                    // Get file size in bytes.
+                    file_data.size = metadata.len() as f64;
+                    // Get file name.
+                    file_data.name = f_path.file_name().unwrap().to_str().unwrap().to_string();
+                    // Create doc.
+                    let result = to_document(&file_data)?;
+                    Bson::Document(result)
+                } else {
+                    Bson::Null
+                }
+            }
+            "ImageField" => {
+                if !default_value.is_null() {
+                    let mut file_data = serde_json::from_value::<ImageData>(default_value.clone())?;
+                    // Define flags to check.
+                    if file_data.path.is_empty() || file_data.url.is_empty() {
+                        Err(format!(
+                            "Model: `{}` > Field: `{}` ; Method: \
+                            `migrat()` => Check the `path` and `url` \
+                            attributes in the `default` field parameter.",
+                            meta.model_name, field_name
+                        ))?
+                    }
+                    // Create path for validation of file.
+                    let path: String = file_data.path.clone();
+                    let f_path = Path::new(path.as_str());
+                    if !f_path.is_file() {
+                        Err(format!(
+                            "Model: `{}` > Field: `{}` ; Method: \
+                                `migrat()` => Image is missing - {}",
+                            meta.model_name, field_name, path
+                        ))?
+                    }
+                    // Get file metadata.
+                    let metadata = f_path.metadata()?;
+                    // Get file size in bytes.
+                    file_data.size = metadata.len() as f64;
+                    // Get file name.
+                    file_data.name = f_path.file_name().unwrap().to_str().unwrap().to_string();
+                    // Get image width and height.
+                    let dimensions = image::image_dimensions(path)?;
+                    file_data.width = dimensions.0 as f64;
+                    file_data.height = dimensions.1 as f64;
+                    // Create doc.
+                    let result = to_document(&file_data)?;
+                    Bson::Document(result)
+                } else {
+                    Bson::Null
+                }
+            }
+            "ChoiceTextMultField" | "ListTextField" => {
+                if !default_value.is_null() {
+                    let val = serde_json::from_value::<Vec<String>>(default_value.clone())?
+                        .iter()
+                        .map(|item| Bson::String(item.clone()))
+                        .collect::<Vec<Bson>>();
+                    Bson::Array(val)
+                } else {
+                    Bson::Null
+                }
+            }
+            "ChoiceI32MultField" => {
+                if !default_value.is_null() {
+                    let val = serde_json::from_value::<Vec<i32>>(default_value.clone())?
+                        .iter()
+                        .map(|item| Bson::Int32(*item))
+                        .collect::<Vec<Bson>>();
+                    Bson::Array(val)
+                } else {
+                    Bson::Null
+                }
+            }
+            "ChoiceU32MultField" | "ChoiceI64MultField" | "ListI64Field" => {
+                if !default_value.is_null() {
+                    let val = serde_json::from_value::<Vec<i64>>(default_value.clone())?
+                        .iter()
+                        .map(|item| mongodb::bson::Bson::Int64(*item))
+                        .collect::<Vec<Bson>>();
+                    Bson::Array(val)
+                } else {
+                    Bson::Null
+                }
+            }
+            "ChoiceF64MultField" | "ListF64Field" => {
+                if !default_value.is_null() {
+                    let val = serde_json::from_value::<Vec<f64>>(default_value.clone())?
+                        .iter()
+                        .map(|item| Bson::Double(*item))
+                        .collect::<Vec<Bson>>();
+                    Bson::Array(val)
+                } else {
+                    Bson::Null
+                }
+            }
+            "ChoiceTextDynField"
+            | "ChoiceTextMultDynField"
+            | "ChoiceI32DynField"
+            | "ChoiceI32MultDynField"
+            | "ChoiceU32DynField"
+            | "ChoiceU32MultDynField"
+            | "ChoiceI64DynField"
+            | "ChoiceI64MultDynField"
+            | "ChoiceF64DynField"
+            | "ChoiceF64MultDynField" => Bson::Null,
+            _ => Err(format!(
+                "Service: `{}` > Model: `{}` ; \
+                        Method: `migrat()` => Invalid Field type.",
+                meta.service_name, meta.model_name
+            ))?,
+        };
+        Ok(value)
+    }
+
+    /// Get a list of files in the directory ( recursively ).
+    // *********************************************************************************************
+    fn media_files(dir_path: &Path, file_list: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+        if !dir_path.is_dir() {
+            return Ok(());
+        }
+        for entry in fs::read_dir(dir_path)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                Self::media_files(&path, file_list)?;
+            } else {
+                file_list.push(path);
+            }
+        }
         Ok(())
     }
+
+    /// Normalize the path for comparison ( `./media/file.txt` == `media/file.txt` ).
+    // *********************************************************************************************
+    fn normalize_path(path: &Path) -> PathBuf {
+        fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    }
 }
@@ -0,0 +1,285 @@
+//! Conversion of field values when the field type changes during migration.
+//!
+//! Built-in conversions: string <-> number, string <-> bool, date string -> DateTime,
+//! scalar -> single-item array, single-item array -> scalar.
+//! For other cases, register a converter for the field via `register_field_converter`.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use mongodb::bson::{spec::BinarySubtype, Binary, Bson};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{models::helpers::Meta, store::FIELD_CONVERTERS};
+
+/// User-supplied converter - (value, old field type, new field type) -> new value.
+/// Hint: Return an error message if the value cannot be converted.
+pub type FieldConverter = Arc<dyn Fn(&Bson, &str, &str) -> Result<Bson, String> + Send + Sync>;
+
+/// A value that could not be converted to the new field type.
+/// Hint: The original value of the field is kept - fix it or register a converter
+/// ( see `register_field_converter` ).
+#[derive(Clone, Debug)]
+pub struct UnconvertibleValue {
+    pub database: String,
+    pub collection: String,
+    pub field_name: String,
+    /// Document identifier `_id`.
+    pub doc_id: Bson,
+    /// Original value from the database.
+    pub value: Bson,
+    pub old_type: String,
+    pub new_type: String,
+    pub error: String,
+}
+
+/// Register a converter for the model field.
+///
+/// # Example:
+///
+/// ```
+/// register_field_converter(&User::key()?, "age", |value, _old_type, _new_type| {
+///     match value {
+///         Bson::String(val) => Ok(Bson::Int64(val.len() as i64)),
+///         _ => Err("Expected a string.".to_string()),
+///     }
+/// })
+/// .await;
+/// ```
+// *************************************************************************************************
+pub async fn register_field_converter<F>(model_key: &str, field_name: &str, converter: F)
+where
+    F: Fn(&Bson, &str, &str) -> Result<Bson, String> + Send + Sync + 'static,
+{
+    let mut converters = FIELD_CONVERTERS.lock().await;
+    converters.insert(
+        (model_key.to_string(), field_name.to_string()),
+        Arc::new(converter),
+    );
+}
+
+/// Get the converter of the model field.
+// *************************************************************************************************
+pub async fn get_field_converter(model_key: &str, field_name: &str) -> Option<FieldConverter> {
+    let converters = FIELD_CONVERTERS.lock().await;
+    converters
+        .get(&(model_key.to_string(), field_name.to_string()))
+        .cloned()
+}
+
+/// Convert the value to the new field type ( built-in conversions ).
+// *************************************************************************************************
+pub fn convert_value(
+    value: &Bson,
+    new_type: &str,
+    field_name: &str,
+    meta: &Meta,
+) -> Result<Bson, String> {
+    if value == &Bson::Null {
+        return Ok(Bson::Null);
+    }
+    let is_dyn = new_type.contains("Dyn");
+    let result = match new_type {
+        "ColorField" | "EmailField" | "PhoneField" | "TextField" | "URLField" | "IPField"
+        | "SlugField" | "TimeField" | "HashField" | "ChoiceTextField" | "ChoiceEnumField"
+        | "ChoiceTextDynField" => to_string(scalar(value)?)?,
+        "I32Field" | "ChoiceI32Field" | "ChoiceI32DynField" => Bson::Int32(to_i32(scalar(value)?)?),
+        "I64Field" | "ChoiceI64Field" | "ChoiceI64DynField" => Bson::Int64(to_i64(scalar(value)?)?),
+        "U32Field" | "ChoiceU32Field" | "ChoiceU32DynField" => Bson::Int64(to_u32(scalar(value)?)?),
+        "F64Field" | "ChoiceF64Field" | "ChoiceF64DynField" => {
+            Bson::Double(to_f64(scalar(value)?)?)
+        }
+        "BoolField" => Bson::Boolean(to_bool(scalar(value)?)?),
+        "DateField" | "DateTimeField" | "HiddenDateTimeField" => to_datetime(scalar(value)?)?,
+        "UUIDField" => to_uuid(scalar(value)?)?,
+        "JsonField" => value.clone(),
+        "ChoiceTextMultField" | "ListTextField" | "ChoiceTextMultDynField" => {
+            to_array(value, to_string)?
+        }
+        "ChoiceI32MultField" | "ChoiceI32MultDynField" => {
+            to_array(value, |val| to_i32(val).map(Bson::Int32))?
+        }
+        "ChoiceI64MultField" | "ListI64Field" | "ChoiceI64MultDynField" => {
+            to_array(value, |val| to_i64(val).map(Bson::Int64))?
+        }
+        "ChoiceU32MultField" | "ChoiceU32MultDynField" => {
+            to_array(value, |val| to_u32(val).map(Bson::Int64))?
+        }
+        "ChoiceF64MultField" | "ListF64Field" | "ChoiceF64MultDynField" => {
+            to_array(value, |val| to_f64(val).map(Bson::Double))?
+        }
+        _ => Err(format!("There is no built-in conversion to `{new_type}`."))?,
+    };
+    // Values of choice fields must match the choices.
+    if new_type.starts_with("Choice") && !is_dyn {
+        check_choices(&result, field_name, meta)?;
+    }
+    Ok(result)
+}
+
+/// Get a scalar value ( a single-item array is unpacked ).
+fn scalar(value: &Bson) -> Result<&Bson, String> {
+    match value {
+        Bson::Array(items) if items.len() == 1 => Ok(&items[0]),
+        Bson::Array(items) => Err(format!(
+            "An array of {} items cannot be converted to a scalar value.",
+            items.len()
+        )),
+        _ => Ok(value),
+    }
+}
+
+fn to_string(value: &Bson) -> Result<Bson, String> {
+    match value {
+        Bson::String(val) => Ok(Bson::String(val.clone())),
+        Bson::Int32(val) => Ok(Bson::String(val.to_string())),
+        Bson::Int64(val) => Ok(Bson::String(val.to_string())),
+        Bson::Double(val) => Ok(Bson::String(val.to_string())),
+        Bson::Boolean(val) => Ok(Bson::String(val.to_string())),
+        _ => Err(format!("Value `{value}` cannot be converted to a string.")),
+    }
+}
+
+fn to_i64(value: &Bson) -> Result<i64, String> {
+    match value {
+        Bson::Int32(val) => Ok(*val as i64),
+        Bson::Int64(val) => Ok(*val),
+        Bson::Double(val) if val.fract() == 0.0 && val.abs() < i64::MAX as f64 => Ok(*val as i64),
+        Bson::String(val) => val
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| format!("String `{val}` cannot be converted to an integer.")),
+        Bson::Boolean(val) => Ok(*val as i64),
+        _ => Err(format!(
+            "Value `{value}` cannot be converted to an integer."
+        )),
+    }
+}
+
+fn to_i32(value: &Bson) -> Result<i32, String> {
+    let val = to_i64(value)?;
+    i32::try_from(val).map_err(|_| format!("Number `{val}` is out of range for i32."))
+}
+
+fn to_u32(value: &Bson) -> Result<i64, String> {
+    let val = to_i64(value)?;
+    u32::try_from(val)
+        .map(|val| val as i64)
+        .map_err(|_| format!("Number `{val}` is out of range for u32."))
+}
+
+fn to_f64(value: &Bson) -> Result<f64, String> {
+    match value {
+        Bson::Int32(val) => Ok(*val as f64),
+        Bson::Int64(val) => Ok(*val as f64),
+        Bson::Double(val) => Ok(*val),
+        Bson::String(val) => val
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|val| val.is_finite())
+            .ok_or_else(|| format!("String `{val}` cannot be converted to a number.")),
+        _ => Err(format!("Value `{value}` cannot be converted to a number.")),
+    }
+}
+
+fn to_bool(value: &Bson) -> Result<bool, String> {
+    match value {
+        Bson::Boolean(val) => Ok(*val),
+        Bson::Int32(val) if *val == 0 || *val == 1 => Ok(*val == 1),
+        Bson::Int64(val) if *val == 0 || *val == 1 => Ok(*val == 1),
+        Bson::String(val) => match val.trim().to_lowercase().as_str() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(format!("String `{val}` cannot be converted to a bool.")),
+        },
+        _ => Err(format!("Value `{value}` cannot be converted to a bool.")),
+    }
+}
+
+fn to_datetime(value: &Bson) -> Result<Bson, String> {
+    match value {
+        Bson::DateTime(_) => Ok(value.clone()),
+        Bson::String(val) => {
+            let val = val.trim();
+            let dt = if let Ok(dt) = DateTime::parse_from_rfc3339(val) {
+                dt.with_timezone(&Utc)
+            } else if let Ok(ndt) = NaiveDateTime::parse_from_str(val, "%Y-%m-%dT%H:%M") {
+                DateTime::<Utc>::from_naive_utc_and_offset(ndt, Utc)
+            } else if let Ok(ndt) = NaiveDateTime::parse_from_str(val, "%Y-%m-%dT%H:%M:%S") {
+                DateTime::<Utc>::from_naive_utc_and_offset(ndt, Utc)
+            } else if let Some(ndt) = NaiveDate::parse_from_str(val, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+            {
+                DateTime::<Utc>::from_naive_utc_and_offset(ndt, Utc)
+            } else {
+                Err(format!(
+                    "String `{val}` cannot be converted to a date. \
+                    Example: 1970-02-28 or 1970-02-28T00:00"
+                ))?
+            };
+            Ok(Bson::DateTime(dt.into()))
+        }
+        _ => Err(format!("Value `{value}` cannot be converted to a date.")),
+    }
+}
+
+fn to_uuid(value: &Bson) -> Result<Bson, String> {
+    match value {
+        Bson::Binary(binary) if binary.subtype == BinarySubtype::Uuid => Ok(value.clone()),
+        Bson::String(val) => {
+            let uuid = Uuid::parse_str(val.trim())
+                .map_err(|_| format!("String `{val}` cannot be converted to a UUID."))?;
+            Ok(Bson::Binary(Binary {
+                subtype: BinarySubtype::Uuid,
+                bytes: uuid.as_bytes().to_vec(),
+            }))
+        }
+        _ => Err(format!("Value `{value}` cannot be converted to a UUID.")),
+    }
+}
+
+/// Convert the value to an array ( a scalar value becomes a single-item array ).
+fn to_array<F>(value: &Bson, convert: F) -> Result<Bson, String>
+where
+    F: Fn(&Bson) -> Result<Bson, String>,
+{
+    let items = match value {
+        Bson::Array(items) => items.iter().map(&convert).collect::<Result<Vec<_>, _>>()?,
+        _ => vec![convert(value)?],
+    };
+    Ok(Bson::Array(items))
+}
+
+/// Check that the values of the choice field match the choices.
+fn check_choices(value: &Bson, field_name: &str, meta: &Meta) -> Result<(), String> {
+    let items = match value {
+        Bson::Array(items) => items.iter().collect::<Vec<_>>(),
+        _ => vec![value],
+    };
+    for item in items {
+        let is_valid = match item {
+            Bson::String(val) => meta
+                .choice_str_map
+                .get(field_name)
+                .map_or(false, |choices| choices.contains(val)),
+            Bson::Int32(val) => meta
+                .choice_i32_map
+                .get(field_name)
+                .map_or(false, |choices| choices.contains(val)),
+            Bson::Int64(val) => meta
+                .choice_i64_map
+                .get(field_name)
+                .map_or(false, |choices| choices.contains(val)),
+            Bson::Double(val) => meta
+                .choice_f64_map
+                .get(field_name)
+                .map_or(false, |choices| choices.contains(val)),
+            _ => false,
+        };
+        if !is_valid {
+            Err(format!("Value `{item}` does not match the choices."))?
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,200 @@
+//! Versioned data migrations ( backfilling fields, splitting values, recomputing data, etc. ).
+//!
+//! Applied migrations are recorded in the `data_migrations` collection of the technical database.
+//! Migrations run in ascending order of versions, under the same lock in the technical database
+//! as `Monitor::migrat()`, so only one instance of the application migrates at a time
+//! and data migrations never run during a schema migration.
+//!
+//! # Example:
+//!
+//! ```
+//! struct BackfillSlugs;
+//!
+//! #[async_trait(?Send)]
+//! impl DataMigration for BackfillSlugs {
+//!     fn version(&self) -> &str {
+//!         "0001_backfill_slugs"
+//!     }
+//!     async fn up(&self, client: &Client) -> Result<(), Box<dyn Error>> {
+//!         ...
+//!     }
+//!     async fn down(&self, client: &Client) -> Result<(), Box<dyn Error>> {
+//!         ...
+//!     }
+//! }
+//!
+//! let migrations: Vec<Box<dyn DataMigration>> = vec![Box::new(BackfillSlugs)];
+//! monitor.migrat(&client).await?;
+//! let applied = monitor.run_data_migrations(&client, &migrations).await?;
+//! // Roll back the last migration.
+//! let rolled_back = monitor.rollback_data_migrations(&client, &migrations, 1).await?;
+//! ```
+
+use async_trait::async_trait;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, DateTime, Document},
+    options::FindOptions,
+    Client,
+};
+use std::{collections::HashSet, error::Error, time::Instant};
+
+use crate::migration::{
+    lock::{MigrationLock, MIGRATION_LOCK_TTL, MIGRAT_LOCK},
+    Monitor,
+};
+
+/// Collection of applied data migrations in the technical database.
+pub const DATA_MIGRATIONS: &str = "data_migrations";
+
+/// User-defined data migration.
+#[async_trait(?Send)]
+pub trait DataMigration {
+    /// Unique version, migrations are applied in ascending order.
+    /// Example: "0001_backfill_slugs"
+    fn version(&self) -> &str;
+
+    /// Apply the migration.
+    async fn up(&self, client: &Client) -> Result<(), Box<dyn Error>>;
+
+    /// Roll back the migration.
+    /// Hint: By default, the migration is irreversible.
+    async fn down(&self, _client: &Client) -> Result<(), Box<dyn Error>> {
+        Err(format!(
+            "Data migration `{}` => The migration is irreversible.",
+            self.version()
+        ))?
+    }
+}
+
+impl<'a> Monitor<'a> {
+    /// Get a list of versions of applied data migrations ( in ascending order ).
+    // *********************************************************************************************
+    pub async fn applied_data_migrations(
+        &self,
+        client: &Client,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let db_green_tech: String = self.green_tech_name()?;
+        let options = FindOptions::builder().sort(doc! {"_id": 1}).build();
+        let mut cursor = client
+            .database(&db_green_tech)
+            .collection::<Document>(DATA_MIGRATIONS)
+            .find(None, options)
+            .await?;
+        let mut versions = Vec::<String>::new();
+        while let Some(doc) = cursor.try_next().await? {
+            versions.push(doc.get_str("_id")?.to_string());
+        }
+        Ok(versions)
+    }
+
+    /// Apply data migrations that have not yet been applied ( in ascending order of versions ).
+    /// Returns a list of versions of applied migrations.
+    /// Hint: If a migration fails, the following migrations are not applied.
+    // *********************************************************************************************
+    pub async fn run_data_migrations(
+        &self,
+        client: &Client,
+        migrations: &[Box<dyn DataMigration>],
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut migrations = migrations
+            .iter()
+            .map(|migration| migration.as_ref())
+            .collect::<Vec<&dyn DataMigration>>();
+        migrations.sort_by(|a, b| a.version().cmp(b.version()));
+        Self::check_versions(&migrations)?;
+        let db_green_tech: String = self.green_tech_name()?;
+        let db = client.database(&db_green_tech);
+        let lock = MigrationLock::acquire(&db, MIGRAT_LOCK, MIGRATION_LOCK_TTL).await?;
+        let result = async {
+            let applied = self.applied_data_migrations(client).await?;
+            let coll = db.collection::<Document>(DATA_MIGRATIONS);
+            let mut versions = Vec::<String>::new();
+            for migration in migrations {
+                let version = migration.version();
+                if applied.iter().any(|item| item == version) {
+                    continue;
+                }
+                let started_at = Instant::now();
+                migration.up(client).await.map_err(|err| {
+                    format!("Data migration `{version}` ; Method: `up()` => {err}")
+                })?;
+                coll.insert_one(
+                    doc! {
+                        "_id": version,
+                        "applied_at": DateTime::now(),
+                        "duration_ms": started_at.elapsed().as_millis() as i64,
+                    },
+                    None,
+                )
+                .await?;
+                versions.push(version.to_string());
+            }
+            Ok::<_, Box<dyn Error>>(versions)
+        }
+        .await;
+        lock.release().await?;
+        result
+    }
+
+    /// Roll back the last `steps` applied data migrations ( in descending order of versions ).
+    /// Returns a list of versions of rolled back migrations.
+    // *********************************************************************************************
+    pub async fn rollback_data_migrations(
+        &self,
+        client: &Client,
+        migrations: &[Box<dyn DataMigration>],
+        steps: usize,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let migrations = migrations
+            .iter()
+            .map(|migration| migration.as_ref())
+            .collect::<Vec<&dyn DataMigration>>();
+        Self::check_versions(&migrations)?;
+        let db_green_tech: String = self.green_tech_name()?;
+        let db = client.database(&db_green_tech);
+        let lock = MigrationLock::acquire(&db, MIGRAT_LOCK, MIGRATION_LOCK_TTL).await?;
+        let result = async {
+            let applied = self.applied_data_migrations(client).await?;
+            let coll = db.collection::<Document>(DATA_MIGRATIONS);
+            let mut versions = Vec::<String>::new();
+            for version in applied.iter().rev().take(steps) {
+                let migration = migrations
+                    .iter()
+                    .find(|migration| migration.version() == version)
+                    .ok_or_else(|| {
+                        format!(
+                            "Data migration `{version}` => \
+                            The migration is applied, but is missing from the list of migrations."
+                        )
+                    })?;
+                migration.down(client).await.map_err(|err| {
+                    format!("Data migration `{version}` ; Method: `down()` => {err}")
+                })?;
+                coll.delete_one(doc! {"_id": version}, None).await?;
+                versions.push(version.clone());
+            }
+            Ok::<_, Box<dyn Error>>(versions)
+        }
+        .await;
+        lock.release().await?;
+        result
+    }
+
+    /// Check the versions of data migrations for emptiness and uniqueness.
+    fn check_versions(migrations: &[&dyn DataMigration]) -> Result<(), Box<dyn Error>> {
+        let mut versions = HashSet::<&str>::new();
+        for migration in migrations {
+            let version = migration.version();
+            if version.is_empty() {
+                Err("Data migrations => An empty version is not allowed.")?
+            }
+            if !versions.insert(version) {
+                Err(format!(
+                    "Data migrations => The version `{version}` is not unique."
+                ))?
+            }
+        }
+        Ok(())
+    }
+}
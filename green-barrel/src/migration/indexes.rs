@@ -0,0 +1,253 @@
+//! Indexes derived from the metadata of the Model.
+//!
+//! Field attributes: `unique`, `index`, `sparse`, `ttl` ( DateField, DateTimeField ).
+//! Model parameters: `indexes`, `unique_indexes` ( compound indexes ).
+//!
+//! The names of the managed indexes start with `gb__`.
+//! `migrat()` creates the declared indexes and drops the managed indexes that are no longer declared,
+//! indexes created by the user ( without the prefix ) are not touched.
+//! Hint: Unique indexes skip empty values ( `null` ), like the validation of the `unique` attribute.
+
+use mongodb::{
+    bson::{doc, Document},
+    error::{ErrorKind, WriteFailure},
+    options::IndexOptions,
+    Collection, IndexModel,
+};
+use regex::Regex;
+use std::{error::Error, time::Duration};
+
+use crate::models::helpers::Meta;
+
+/// Name prefix of the managed indexes.
+pub const INDEX_PREFIX: &str = "gb__";
+
+/// Get a list of indexes declared in the Model.
+// *************************************************************************************************
+pub fn declared_indexes(meta: &Meta) -> Vec<IndexModel> {
+    let mut indexes = Vec::<IndexModel>::new();
+    // Indexes of fields.
+    for field_name in meta.fields_name.iter() {
+        if field_name == "hash" || meta.ignore_fields.contains(field_name) {
+            continue;
+        }
+        let field = &meta.model_json[field_name];
+        let is_unique = field["unique"].as_bool().unwrap_or_default();
+        let is_index = field["index"].as_bool().unwrap_or_default();
+        let is_sparse = field["sparse"].as_bool().unwrap_or_default();
+        let ttl = field["ttl"].as_u64().unwrap_or_default();
+        if !is_unique && !is_index && ttl == 0 {
+            continue;
+        }
+        let field_type = meta
+            .field_type_map
+            .get(field_name)
+            .map_or("", String::as_str);
+        let keys = vec![(field_name.clone(), 1)];
+        indexes.push(index_model(&keys, is_unique, is_sparse, ttl, &[field_type]));
+    }
+    // Compound indexes.
+    for (compound_indexes, is_unique) in [(&meta.indexes, false), (&meta.unique_indexes, true)] {
+        for keys in compound_indexes.iter() {
+            let field_types = keys
+                .iter()
+                .map(|(field_name, _)| {
+                    meta.field_type_map
+                        .get(field_name)
+                        .map_or("", String::as_str)
+                })
+                .collect::<Vec<&str>>();
+            indexes.push(index_model(keys, is_unique, false, 0, &field_types));
+        }
+    }
+    indexes
+}
+
+/// Create an index model with a name that describes the keys and options.
+/// Example: `gb__username_1__unique`
+fn index_model(
+    keys: &[(String, i32)],
+    is_unique: bool,
+    is_sparse: bool,
+    ttl: u64,
+    field_types: &[&str],
+) -> IndexModel {
+    let mut name = format!(
+        "{INDEX_PREFIX}{}",
+        keys.iter()
+            .map(|(field_name, direction)| format!("{field_name}_{direction}"))
+            .collect::<Vec<String>>()
+            .join("_")
+    );
+    let mut key_doc = Document::new();
+    for (field_name, direction) in keys.iter() {
+        key_doc.insert(field_name, direction);
+    }
+    let mut options = IndexOptions::default();
+    if is_unique {
+        name.push_str("__unique");
+        options.unique = Some(true);
+        // Documents with empty values are not included in the index.
+        let mut filter = Document::new();
+        for ((field_name, _), field_type) in keys.iter().zip(field_types.iter()) {
+            if let Some(bson_type) = bson_type_alias(field_type) {
+                filter.insert(field_name, doc! {"$type": bson_type});
+            }
+        }
+        if !filter.is_empty() {
+            options.partial_filter_expression = Some(filter);
+        }
+    } else if is_sparse {
+        name.push_str("__sparse");
+        options.sparse = Some(true);
+    }
+    if ttl > 0 {
+        name.push_str(&format!("__ttl{ttl}"));
+        options.expire_after = Some(Duration::from_secs(ttl));
+    }
+    options.name = Some(name);
+    IndexModel::builder().keys(key_doc).options(options).build()
+}
+
+/// Get the alias of the BSON type of the field values ( for the `$type` operator ).
+fn bson_type_alias(field_type: &str) -> Option<&'static str> {
+    match field_type {
+        "ColorField"
+        | "EmailField"
+        | "PhoneField"
+        | "TextField"
+        | "URLField"
+        | "IPField"
+        | "SlugField"
+        | "TimeField"
+        | "ChoiceTextField"
+        | "ChoiceEnumField"
+        | "ChoiceTextDynField"
+        | "ChoiceTextMultField"
+        | "ChoiceTextMultDynField"
+        | "ListTextField" => Some("string"),
+        "I32Field"
+        | "ChoiceI32Field"
+        | "ChoiceI32DynField"
+        | "ChoiceI32MultField"
+        | "ChoiceI32MultDynField" => Some("int"),
+        "I64Field"
+        | "U32Field"
+        | "ChoiceI64Field"
+        | "ChoiceI64DynField"
+        | "ChoiceI64MultField"
+        | "ChoiceI64MultDynField"
+        | "ChoiceU32Field"
+        | "ChoiceU32DynField"
+        | "ChoiceU32MultField"
+        | "ChoiceU32MultDynField"
+        | "ListI64Field" => Some("long"),
+        "F64Field"
+        | "ChoiceF64Field"
+        | "ChoiceF64DynField"
+        | "ChoiceF64MultField"
+        | "ChoiceF64MultDynField"
+        | "ListF64Field" => Some("double"),
+        "DateField" | "DateTimeField" | "HiddenDateTimeField" => Some("date"),
+        "UUIDField" => Some("binData"),
+        "BoolField" => Some("bool"),
+        _ => None,
+    }
+}
+
+/// Create the declared indexes and drop the managed indexes that are no longer declared.
+/// Returns ( names of created indexes, names of dropped indexes ).
+// *************************************************************************************************
+pub(crate) async fn sync_indexes(
+    coll: &Collection<Document>,
+    meta: &Meta,
+    is_dry_run: bool,
+) -> Result<(Vec<String>, Vec<String>), Box<dyn Error>> {
+    let declared = declared_indexes(meta);
+    let declared_names = declared
+        .iter()
+        .filter_map(|index| {
+            index
+                .options
+                .as_ref()
+                .and_then(|options| options.name.clone())
+        })
+        .collect::<Vec<String>>();
+    let existing_names = match coll.list_index_names().await {
+        Ok(names) => names,
+        // The collection does not exist yet ( dry run ).
+        Err(err) if matches!(err.kind.as_ref(), ErrorKind::Command(command_error) if command_error.code == 26) => {
+            Vec::new()
+        }
+        Err(err) => Err(err)?,
+    };
+    // Drop the managed indexes that are no longer declared.
+    // Hint: First, so that an index with changed options can be recreated on the same keys.
+    let mut dropped = Vec::<String>::new();
+    for name in existing_names.iter() {
+        if name.starts_with(INDEX_PREFIX) && !declared_names.contains(name) {
+            if !is_dry_run {
+                coll.drop_index(name, None).await?;
+            }
+            dropped.push(name.clone());
+        }
+    }
+    // Create the missing indexes.
+    let mut created = Vec::<String>::new();
+    for (index, name) in declared.into_iter().zip(declared_names) {
+        if existing_names.contains(&name) {
+            continue;
+        }
+        if !is_dry_run {
+            coll.create_index(index, None).await.map_err(|err| {
+                format!(
+                    "Model: `{}` ; Method: `migrat()` => Failed to create index `{name}` - {err}",
+                    meta.model_name
+                )
+            })?;
+        }
+        created.push(name);
+    }
+    Ok((created, dropped))
+}
+
+/// Get the names of the fields whose values violate a unique index.
+/// Hint: Returns an empty list if the error is not a duplicate key error.
+// *************************************************************************************************
+pub fn duplicate_key_fields(
+    err: &mongodb::error::Error,
+    fields_name: &[String],
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let message = match err.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(write_error)) if write_error.code == 11000 => {
+            write_error.message.as_str()
+        }
+        _ => return Ok(Vec::new()),
+    };
+    parse_duplicate_key_message(message, fields_name)
+}
+
+/// Get the names of the fields from the message of a duplicate key error.
+/// Example: `E11000 duplicate key error ... index: gb__username_1__unique dup key: { username: "user_1" }`
+// *************************************************************************************************
+pub fn parse_duplicate_key_message(
+    message: &str,
+    fields_name: &[String],
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let dup_key = match message.find("dup key:") {
+        Some(pos) => &message[pos..],
+        None => return Ok(Vec::new()),
+    };
+    // Remove the values, so that the keys can be found.
+    let re_value = Regex::new(r#""(?:[^"\\]|\\.)*""#)?;
+    let dup_key = re_value.replace_all(dup_key, "");
+    let re_key = Regex::new(r"[{,]\s*([_a-zA-Z][_a-zA-Z\d]*)\s*:")?;
+    let mut field_names = Vec::<String>::new();
+    for caps in re_key.captures_iter(&dup_key) {
+        let field_name = caps[1].to_string();
+        if fields_name.contains(&field_name) && !field_names.contains(&field_name) {
+            field_names.push(field_name);
+        }
+    }
+    Ok(field_names)
+}
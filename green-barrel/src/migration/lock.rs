@@ -0,0 +1,171 @@
+//! Lease-based lock in the technical database,
+//! so that only one instance of the application migrates at a time.
+//!
+//! The lock document holds the owner and the lease expiration time.
+//! While the lock is held, a background task extends the lease ( heartbeat ).
+//! If the instance crashes, the lease expires after the TTL and the lock can be acquired again.
+//! Hint: Expiration times are calculated from the clocks of the application instances.
+
+use mongodb::{
+    bson::{doc, DateTime, Document},
+    error::{ErrorKind, WriteFailure},
+    options::UpdateOptions,
+    Collection, Database,
+};
+use std::{
+    error::Error,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{task::JoinHandle, time::sleep};
+use uuid::Uuid;
+
+/// Collection of locks in the technical database.
+pub const MIGRATION_LOCKS: &str = "migration_locks";
+
+/// Name of the lock for migrations of the project.
+/// Hint: Schema migrations ( `Monitor::migrat()` ) and data migrations
+/// ( `Monitor::run_data_migrations()` ) use the same lock, so they never run at the same time.
+pub const MIGRAT_LOCK: &str = "monitor_migrat";
+
+/// Lease duration of the lock.
+pub const MIGRATION_LOCK_TTL: Duration = Duration::from_secs(60);
+
+/// Interval between attempts to acquire a busy lock.
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Lease-based lock in the technical database.
+/// Hint: The lock must be released with `release()`,
+/// otherwise it is released when the lease expires.
+pub struct MigrationLock {
+    coll: Collection<Document>,
+    name: String,
+    owner: String,
+    is_lost: Arc<AtomicBool>,
+    heartbeat: JoinHandle<()>,
+}
+
+impl MigrationLock {
+    /// Try to acquire the lock.
+    /// Hint: Returns None if the lock is held by another instance.
+    pub async fn try_acquire(
+        db: &Database,
+        name: &str,
+        ttl: Duration,
+    ) -> Result<Option<Self>, Box<dyn Error>> {
+        if ttl.as_millis() < 3 {
+            Err(format!("Migration lock `{name}` => The TTL is too small."))?
+        }
+        let coll = db.collection::<Document>(MIGRATION_LOCKS);
+        let owner = Uuid::new_v4().to_string();
+        let now = DateTime::now();
+        // The lock is acquired if it does not exist or its lease has expired.
+        // If the lease of another owner is active, the upsert fails with a duplicate key error.
+        let filter = doc! {"_id": name, "expires_at": {"$lt": now}};
+        let update = doc! {
+            "$set": {
+                "owner": &owner,
+                "locked_at": now,
+                "expires_at": Self::expires_at(ttl),
+            }
+        };
+        let options = UpdateOptions::builder().upsert(true).build();
+        if let Err(err) = coll.update_one(filter, update, options).await {
+            if is_duplicate_key_error(&err) {
+                return Ok(None);
+            }
+            Err(err)?
+        }
+        // Extend the lease in the background.
+        let is_lost = Arc::new(AtomicBool::new(false));
+        let heartbeat = tokio::spawn({
+            let coll = coll.clone();
+            let name = name.to_string();
+            let owner = owner.clone();
+            let is_lost = is_lost.clone();
+            async move {
+                loop {
+                    sleep(ttl / 3).await;
+                    let result = coll
+                        .update_one(
+                            doc! {"_id": &name, "owner": &owner},
+                            doc! {"$set": {"expires_at": Self::expires_at(ttl)}},
+                            None,
+                        )
+                        .await;
+                    // Network errors are retried until the lease expires.
+                    if matches!(result, Ok(ref result) if result.matched_count == 0) {
+                        is_lost.store(true, Ordering::SeqCst);
+                        break;
+                    }
+                }
+            }
+        });
+        Ok(Some(Self {
+            coll,
+            name: name.to_string(),
+            owner,
+            is_lost,
+            heartbeat,
+        }))
+    }
+
+    /// Acquire the lock, waiting until it is released by another instance
+    /// ( or its lease expires ).
+    pub async fn acquire(db: &Database, name: &str, ttl: Duration) -> Result<Self, Box<dyn Error>> {
+        loop {
+            if let Some(lock) = Self::try_acquire(db, name, ttl).await? {
+                return Ok(lock);
+            }
+            sleep(RETRY_INTERVAL).await;
+        }
+    }
+
+    /// Whether the lease has been taken over by another instance
+    /// ( for example, the heartbeat could not reach the server before the lease expired ).
+    pub fn is_lost(&self) -> bool {
+        self.is_lost.load(Ordering::SeqCst)
+    }
+
+    /// Release the lock.
+    /// Hint: Returns an error if the lease was lost while the lock was held.
+    pub async fn release(self) -> Result<(), Box<dyn Error>> {
+        self.heartbeat.abort();
+        let deleted_count = self
+            .coll
+            .delete_one(doc! {"_id": &self.name, "owner": &self.owner}, None)
+            .await?
+            .deleted_count;
+        if deleted_count == 0 || self.is_lost() {
+            Err(format!(
+                "Migration lock `{}` => The lease expired while the lock was held, \
+                another instance could migrate at the same time.",
+                self.name
+            ))?
+        }
+        Ok(())
+    }
+
+    /// Expiration time of the lease.
+    fn expires_at(ttl: Duration) -> DateTime {
+        DateTime::from_millis(DateTime::now().timestamp_millis() + ttl.as_millis() as i64)
+    }
+}
+
+impl Drop for MigrationLock {
+    fn drop(&mut self) {
+        // Stop the heartbeat, the lease expires after the TTL.
+        self.heartbeat.abort();
+    }
+}
+
+/// Whether the error is a duplicate key error ( code 11000 ).
+pub fn is_duplicate_key_error(err: &mongodb::error::Error) -> bool {
+    matches!(
+        err.kind.as_ref(),
+        ErrorKind::Write(WriteFailure::WriteError(write_error)) if write_error.code == 11000
+    )
+}
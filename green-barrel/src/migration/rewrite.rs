@@ -0,0 +1,170 @@
+//! Batched rewrite of documents when the fields of the Model change.
+//!
+//! Documents are read in ascending order of `_id` and written in batches
+//! ( one `update` command with many statements per batch ).
+//! After each batch, the last processed `_id` is saved in the `migration_progress` collection
+//! of the technical database, so an interrupted migration continues from where it stopped.
+//!
+//! # Example:
+//!
+//! ```
+//! set_rewrite_progress_callback(|progress| {
+//!     println!(
+//!         "{}.{} - {}/{}",
+//!         progress.database, progress.collection, progress.processed, progress.total
+//!     );
+//! })
+//! .await;
+//! monitor.migrat(&client).await?;
+//! ```
+
+use mongodb::{
+    bson::{doc, Bson, DateTime, Document},
+    options::UpdateOptions,
+    Collection, Database,
+};
+use std::{error::Error, sync::Arc};
+
+use crate::store::REWRITE_PROGRESS_CALLBACK;
+
+/// Collection of checkpoints of interrupted rewrites in the technical database.
+pub const MIGRATION_PROGRESS: &str = "migration_progress";
+
+/// Number of documents in one batch.
+pub const REWRITE_BATCH_SIZE: u32 = 1000;
+
+/// Progress of the rewrite of the collection.
+#[derive(Clone, Debug)]
+pub struct RewriteProgress {
+    pub database: String,
+    pub collection: String,
+    /// Number of processed documents ( including those processed before the interruption ).
+    pub processed: u64,
+    /// Estimated number of documents in the collection.
+    pub total: u64,
+    /// The rewrite continues from a saved checkpoint.
+    pub is_resumed: bool,
+}
+
+/// User-supplied handler of the rewrite progress.
+pub type RewriteProgressCallback = Arc<dyn Fn(&RewriteProgress) + Send + Sync>;
+
+/// Set a handler of the rewrite progress.
+/// Hint: If no handler is set, the progress is not reported.
+// *************************************************************************************************
+pub async fn set_rewrite_progress_callback<F>(callback: F)
+where
+    F: Fn(&RewriteProgress) + Send + Sync + 'static,
+{
+    let mut progress_callback = REWRITE_PROGRESS_CALLBACK.lock().await;
+    *progress_callback = Some(Arc::new(callback));
+}
+
+/// Pass the progress to the handler ( if it is set ).
+pub(crate) async fn report_progress(progress: &RewriteProgress) {
+    let callback = REWRITE_PROGRESS_CALLBACK.lock().await.clone();
+    if let Some(callback) = callback {
+        callback(progress);
+    }
+}
+
+/// Checkpoint of the rewrite of the collection in the technical database.
+pub(crate) struct Checkpoint {
+    coll: Collection<Document>,
+    id: String,
+    /// Description of the field changes -
+    /// the checkpoint is valid only for the same changes.
+    changes: String,
+}
+
+impl Checkpoint {
+    pub(crate) fn new(
+        green_tech_db: &Database,
+        database: &str,
+        collection: &str,
+        changes: String,
+    ) -> Self {
+        Self {
+            coll: green_tech_db.collection::<Document>(MIGRATION_PROGRESS),
+            id: format!("{database}.{collection}"),
+            changes,
+        }
+    }
+
+    /// Get the last processed `_id` and the number of processed documents.
+    pub(crate) async fn load(&self) -> Result<Option<(Bson, u64)>, Box<dyn Error>> {
+        let checkpoint = self
+            .coll
+            .find_one(doc! {"_id": &self.id, "changes": &self.changes}, None)
+            .await?;
+        if let Some(checkpoint) = checkpoint {
+            if let Some(last_id) = checkpoint.get("last_id") {
+                let processed = checkpoint.get_i64("processed").unwrap_or_default();
+                return Ok(Some((last_id.clone(), processed as u64)));
+            }
+        }
+        Ok(None)
+    }
+
+    pub(crate) async fn save(&self, last_id: &Bson, processed: u64) -> Result<(), Box<dyn Error>> {
+        let options = UpdateOptions::builder().upsert(true).build();
+        self.coll
+            .update_one(
+                doc! {"_id": &self.id},
+                doc! {
+                    "$set": {
+                        "changes": &self.changes,
+                        "last_id": last_id,
+                        "processed": processed as i64,
+                        "updated_at": DateTime::now(),
+                    }
+                },
+                options,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn clear(&self) -> Result<(), Box<dyn Error>> {
+        self.coll.delete_one(doc! {"_id": &self.id}, None).await?;
+        Ok(())
+    }
+}
+
+/// Write a batch of updates with one `update` command.
+/// Hint: updates - (_id, fields to set), documents without fields to set are skipped.
+pub(crate) async fn write_batch(
+    db: &Database,
+    collection: &str,
+    updates: &[(Bson, Document)],
+) -> Result<(), Box<dyn Error>> {
+    let statements = updates
+        .iter()
+        .filter(|(_, fields)| !fields.is_empty())
+        .map(|(id, fields)| doc! {"q": {"_id": id}, "u": {"$set": fields}})
+        .collect::<Vec<Document>>();
+    if statements.is_empty() {
+        return Ok(());
+    }
+    let result = db
+        .run_command(
+            doc! {
+                "update": collection,
+                "updates": statements,
+                "ordered": false,
+            },
+            None,
+        )
+        .await?;
+    if let Ok(write_errors) = result.get_array("writeErrors") {
+        if !write_errors.is_empty() {
+            Err(format!(
+                "Migration: `{}.{collection}` => Failed to update {} documents - {}",
+                db.name(),
+                write_errors.len(),
+                write_errors[0]
+            ))?
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,213 @@
+//! `$jsonSchema` collection validator derived from the metadata of the Model.
+//!
+//! Data written outside green-barrel ( other services, the mongo shell ) bypasses `check()`,
+//! the validator lets the database enforce the contract of the Model too.
+//! `migrat()` applies the validator with `collMod`,
+//! the model parameters `validation_level` and `validation_action` configure how it is applied.
+//!
+//! Hint: Not required fields allow `null` ( empty values are saved as `null` ).
+//! Password fields are not checked for length and regex - hashes are saved.
+
+use futures::stream::TryStreamExt;
+use mongodb::{
+    bson::{doc, to_bson, Bson, Document},
+    Database,
+};
+use serde_json::Value;
+use std::error::Error;
+
+use crate::models::helpers::Meta;
+
+/// Get the `$jsonSchema` validator of the Model.
+// *************************************************************************************************
+pub fn json_schema_validator(meta: &Meta) -> Result<Document, Box<dyn Error>> {
+    let mut required = Vec::<String>::new();
+    let mut properties = Document::new();
+    for field_name in meta.fields_name.iter() {
+        if field_name == "hash" || meta.ignore_fields.contains(field_name) {
+            continue;
+        }
+        let field = &meta.model_json[field_name];
+        let is_required = field["required"].as_bool().unwrap_or_default();
+        if is_required {
+            required.push(field_name.clone());
+        }
+        if let Some(schema) = field_schema(field_name, field, meta, is_required)? {
+            properties.insert(field_name, schema);
+        }
+    }
+    let mut schema = doc! {
+        "bsonType": "object",
+        "properties": properties,
+    };
+    if !required.is_empty() {
+        schema.insert("required", required);
+    }
+    Ok(doc! {"$jsonSchema": schema})
+}
+
+/// Get the schema of the field value.
+/// Hint: Returns None if the value can be of any type ( JsonField ).
+fn field_schema(
+    field_name: &str,
+    field: &Value,
+    meta: &Meta,
+    is_required: bool,
+) -> Result<Option<Document>, Box<dyn Error>> {
+    let field_type = meta
+        .field_type_map
+        .get(field_name)
+        .map_or("", String::as_str);
+    let value_type = meta
+        .field_value_type_map
+        .get(field_name)
+        .map_or("", String::as_str);
+    // Type of the value in the database.
+    let bson_type = match field_type {
+        "DateField" | "DateTimeField" | "HiddenDateTimeField" => "date",
+        "UUIDField" => "binData",
+        "FileField" | "ImageField" => "object",
+        _ => match value_type {
+            "String" => "string",
+            "i32" => "int",
+            "u32" | "i64" => "long",
+            "f64" => "double",
+            "bool" => "bool",
+            _ if value_type.starts_with("Vec<") => "array",
+            _ => return Ok(None),
+        },
+    };
+    let mut schema = Document::new();
+    if is_required {
+        schema.insert("bsonType", bson_type);
+    } else {
+        schema.insert("bsonType", vec![bson_type, "null"]);
+    }
+    // Values of choice fields must match the choices.
+    let choices = if field_type.starts_with("Choice") && !field_type.contains("Dyn") {
+        choices(field_name, meta).filter(|choices| !choices.is_empty())
+    } else {
+        None
+    };
+    if bson_type == "array" {
+        let item_type = match value_type {
+            "Vec<String>" => "string",
+            "Vec<i32>" => "int",
+            "Vec<u32>" | "Vec<i64>" => "long",
+            _ => "double",
+        };
+        let mut items = doc! {"bsonType": item_type};
+        if let Some(choices) = choices {
+            items.insert("enum", choices);
+        } else {
+            insert_limits(&mut items, field, field_type)?;
+        }
+        schema.insert("items", items);
+        if let Some(min_items) = field["min_items"].as_u64().filter(|val| *val > 0) {
+            schema.insert("minItems", min_items as i64);
+        }
+        if let Some(max_items) = field["max_items"].as_u64().filter(|val| *val > 0) {
+            schema.insert("maxItems", max_items as i64);
+        }
+    } else if let Some(mut choices) = choices {
+        if !is_required {
+            choices.push(Bson::Null);
+        }
+        schema.insert("enum", choices);
+    } else {
+        insert_limits(&mut schema, field, field_type)?;
+    }
+    Ok(Some(schema))
+}
+
+/// Get the choices of the field from the choice maps.
+fn choices(field_name: &str, meta: &Meta) -> Option<Vec<Bson>> {
+    if let Some(choices) = meta.choice_str_map.get(field_name) {
+        Some(
+            choices
+                .iter()
+                .map(|val| Bson::String(val.clone()))
+                .collect(),
+        )
+    } else if let Some(choices) = meta.choice_i32_map.get(field_name) {
+        Some(choices.iter().map(|val| Bson::Int32(*val)).collect())
+    } else if let Some(choices) = meta.choice_i64_map.get(field_name) {
+        Some(choices.iter().map(|val| Bson::Int64(*val)).collect())
+    } else {
+        meta.choice_f64_map
+            .get(field_name)
+            .map(|choices| choices.iter().map(|val| Bson::Double(*val)).collect())
+    }
+}
+
+/// Insert the limits of the value - minLength, maxLength, pattern, minimum, maximum.
+fn insert_limits(
+    schema: &mut Document,
+    field: &Value,
+    field_type: &str,
+) -> Result<(), Box<dyn Error>> {
+    // The hash of the password is saved, slugs and hashes are generated.
+    if ["PasswordField", "SlugField", "HashField"].contains(&field_type) {
+        return Ok(());
+    }
+    if let Some(minlength) = field["minlength"].as_u64().filter(|val| *val > 0) {
+        schema.insert("minLength", minlength as i64);
+    }
+    if let Some(maxlength) = field["maxlength"].as_u64().filter(|val| *val > 0) {
+        schema.insert("maxLength", maxlength as i64);
+    }
+    if let Some(regex) = field["regex"].as_str().filter(|val| !val.is_empty()) {
+        schema.insert("pattern", regex);
+    }
+    // Hint: For date fields, `min` and `max` are strings.
+    if field["min"].is_number() {
+        schema.insert("minimum", to_bson(&field["min"])?);
+    }
+    if field["max"].is_number() {
+        schema.insert("maximum", to_bson(&field["max"])?);
+    }
+    Ok(())
+}
+
+/// Apply the `$jsonSchema` validator to the collection of the Model ( `collMod` ).
+/// Returns true if the validator ( or its options ) has changed.
+// *************************************************************************************************
+pub(crate) async fn sync_validator(
+    db: &Database,
+    meta: &Meta,
+    is_dry_run: bool,
+) -> Result<bool, Box<dyn Error>> {
+    let validator = json_schema_validator(meta)?;
+    // Compare with the current validator of the collection.
+    let mut cursor = db
+        .list_collections(doc! {"name": &meta.collection_name}, None)
+        .await?;
+    if let Some(spec) = cursor.try_next().await? {
+        let options = spec.options;
+        if options.validator.as_ref() == Some(&validator)
+            && to_bson(&options.validation_level)? == Bson::String(meta.validation_level.clone())
+            && to_bson(&options.validation_action)? == Bson::String(meta.validation_action.clone())
+        {
+            return Ok(false);
+        }
+    }
+    if !is_dry_run {
+        db.run_command(
+            doc! {
+                "collMod": &meta.collection_name,
+                "validator": validator,
+                "validationLevel": &meta.validation_level,
+                "validationAction": &meta.validation_action,
+            },
+            None,
+        )
+        .await
+        .map_err(|err| {
+            format!(
+                "Model: `{}` ; Method: `migrat()` => Failed to apply the validator - {err}",
+                meta.model_name
+            )
+        })?;
+    }
+    Ok(true)
+}
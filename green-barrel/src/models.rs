@@ -8,7 +8,10 @@ pub mod db_query_api;
 pub mod fixtures;
 pub mod helpers;
 pub mod hooks;
+pub mod imaging;
 pub mod output_data;
+pub mod password;
+pub mod schema;
 pub mod validation;
 
 use async_trait::async_trait;
@@ -11,7 +11,12 @@ use serde_json::Value;
 use std::{collections::HashMap, convert::TryFrom, error::Error};
 
 use crate::{
-    models::{converters::Converters, helpers::ControlArr, Main},
+    models::{
+        converters::Converters,
+        helpers::ControlArr,
+        schema::{model_json_schema, model_openapi_schema},
+        Main,
+    },
     store::METADATA,
 };
 
@@ -70,7 +75,7 @@ pub trait Caching: Main + Converters {
         let mut choices_f64_map = HashMap::<String, Vec<f64>>::new();
         for (field_name, field_type) in field_type_map {
             if let Some(choices) = model_json.get(field_name).unwrap().get("choices") {
-                if field_type.contains("Text") {
+                if field_type.contains("Text") || field_type == "ChoiceEnumField" {
                     let choices = choices
                         .as_array()
                         .unwrap()
@@ -179,6 +184,58 @@ pub trait Caching: Main + Converters {
         ))?
     }
 
+    /// Get the JSON Schema of the Model ( values of fields ).
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let json_schema = User::json_schema().await?;
+    /// println!("{json_schema}");
+    /// ```
+    ///
+    async fn json_schema() -> Result<Value, Box<dyn Error>> {
+        // Get a key to access the metadata store.
+        let key = Self::key()?;
+        // Get metadata store.
+        let metadata = METADATA.lock().await;
+        // Get metadata of Model.
+        if let Some(meta) = metadata.get(&key) {
+            return model_json_schema(meta);
+        }
+        //
+        Err(format!(
+            "Model key: `{key}` ; Method: `json_schema()` => \
+             Failed to get data from cache.",
+        ))?
+    }
+
+    /// Get the OpenAPI 3 schema object of the Model ( for `components/schemas` ).
+    // *********************************************************************************************
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let openapi_schema = User::openapi_schema().await?;
+    /// println!("{openapi_schema}");
+    /// ```
+    ///
+    async fn openapi_schema() -> Result<Value, Box<dyn Error>> {
+        // Get a key to access the metadata store.
+        let key = Self::key()?;
+        // Get metadata store.
+        let metadata = METADATA.lock().await;
+        // Get metadata of Model.
+        if let Some(meta) = metadata.get(&key) {
+            return model_openapi_schema(meta);
+        }
+        //
+        Err(format!(
+            "Model key: `{key}` ; Method: `openapi_schema()` => \
+             Failed to get data from cache.",
+        ))?
+    }
+
     /// Update data for dynamic fields.
     /// A more convenient use of these types of fields is implemented in the Green Panel project:
     /// https://github.com/kebasyaty/green-panel
@@ -556,12 +613,12 @@ pub trait Caching: Main + Converters {
                         let tmp_arr_bson = doc_from_db.get_array(const_field_name)?;
                         truncated_arr_bson = tmp_arr_bson
                             .iter()
-                            .cloned()
                             .filter(|item| {
                                 const_control_arr
                                     .control_arr_str()
                                     .contains(&item.as_str().unwrap())
                             })
+                            .cloned()
                             .collect();
                         if truncated_arr_bson.len() != tmp_arr_bson.len() {
                             is_changed = true;
@@ -570,12 +627,12 @@ pub trait Caching: Main + Converters {
                         let tmp_arr_bson = doc_from_db.get_array(const_field_name)?;
                         truncated_arr_bson = tmp_arr_bson
                             .iter()
-                            .cloned()
                             .filter(|item| {
                                 const_control_arr
                                     .control_arr_i32()
                                     .contains(&item.as_i32().unwrap())
                             })
+                            .cloned()
                             .collect();
                         if truncated_arr_bson.len() != tmp_arr_bson.len() {
                             is_changed = true;
@@ -584,12 +641,12 @@ pub trait Caching: Main + Converters {
                         let tmp_arr_bson = doc_from_db.get_array(const_field_name)?;
                         truncated_arr_bson = tmp_arr_bson
                             .iter()
-                            .cloned()
                             .filter(|item| {
                                 const_control_arr
                                     .control_arr_i64()
                                     .contains(&item.as_i64().unwrap())
                             })
+                            .cloned()
                             .collect();
                         if truncated_arr_bson.len() != tmp_arr_bson.len() {
                             is_changed = true;
@@ -598,12 +655,12 @@ pub trait Caching: Main + Converters {
                         let tmp_arr_bson = doc_from_db.get_array(const_field_name)?;
                         truncated_arr_bson = tmp_arr_bson
                             .iter()
-                            .cloned()
                             .filter(|item| {
                                 const_control_arr
                                     .control_arr_f64()
                                     .contains(&item.as_f64().unwrap())
                             })
+                            .cloned()
                             .collect();
                         if truncated_arr_bson.len() != tmp_arr_bson.len() {
                             is_changed = true;
@@ -646,7 +703,7 @@ pub trait Caching: Main + Converters {
                 //
                 if is_changed {
                     // Update the document in the database.
-                    let query = doc! {"_id": doc_from_db.get_object_id("_id")?};
+                    let query = doc! {"_id": doc_from_db.get("_id").unwrap()};
                     coll.update_one(query, doc_from_db, None).await?;
                 }
             }
@@ -9,6 +9,9 @@ use mongodb::{
 };
 use serde_json::Value;
 use std::{collections::HashMap, error::Error};
+use uuid::Uuid;
+
+use crate::models::helpers::IdType;
 
 /// Helper methods for converting output data (use in the commons.rs module).
 #[async_trait(?Send)]
@@ -32,7 +35,7 @@ pub trait Converters {
                 accumula_doc.insert(
                     field_name,
                     if val_bson.element_type() != ElementType::Null {
-                        Bson::String(val_bson.as_object_id().unwrap().to_hex())
+                        Bson::String(IdType::to_hash(val_bson)?)
                     } else {
                         Err(format!(
                             "Model: `{}` > Field: `hash` ; Method: `to_prepared_doc()` => \
@@ -102,6 +105,24 @@ pub trait Converters {
                         Bson::Null
                     },
                 );
+            } else if field_type == "UUIDField" {
+                let val_bson = doc.get(field_name).unwrap();
+                accumula_doc.insert(
+                    field_name,
+                    if val_bson.element_type() != ElementType::Null {
+                        if let Bson::Binary(bin) = val_bson {
+                            Bson::String(Uuid::from_slice(&bin.bytes)?.hyphenated().to_string())
+                        } else {
+                            Err(format!(
+                                "Model: `{}` > Field: `{}` ; Method: `to_prepared_doc()` => \
+                                    The value is not a binary UUID.",
+                                model_name, field_name
+                            ))?
+                        }
+                    } else {
+                        Bson::Null
+                    },
+                );
             } else {
                 let val_bson = doc.get(field_name).unwrap();
                 accumula_doc.insert(field_name, val_bson);
@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use futures::stream::StreamExt;
 use mongodb::{
     bson::{doc, document::Document, Bson},
+    gridfs::GridFsDownloadStream,
     options::{
         AggregateOptions, CountOptions, CreateIndexOptions, DeleteOptions, DistinctOptions,
         DropCollectionOptions, DropIndexOptions, EstimatedDocumentCountOptions,
@@ -17,6 +18,7 @@ use std::error::Error;
 
 use crate::{
     models::{caching::Caching, converters::Converters, output_data::OutputData, Main},
+    storage::{GridFsStorage, GRIDFS_BUCKET},
     store::METADATA,
 };
 
@@ -967,6 +969,46 @@ pub trait QCommons: Main + Caching + Converters {
         Ok(coll.find_one_and_delete(filter, options).await?)
     }
 
+    /// Opens a stream for reading a file from the GridFS bucket of the Model
+    /// ( FileField and ImageField with `storage: "gridfs"` ).
+    /// Hint: `path` is the GridFS file identifier from FileData/ImageData.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use futures::io::AsyncReadExt;
+    ///
+    /// let mut stream = ModelName::open_media_stream(&client, &image_data.path_sm).await?;
+    /// let mut buf = Vec::new();
+    /// stream.read_to_end(&mut buf).await?;
+    /// ```
+    ///
+    async fn open_media_stream(
+        client: &Client,
+        path: &str,
+    ) -> Result<GridFsDownloadStream, Box<dyn Error>>
+    where
+        Self: Serialize + DeserializeOwned + Sized,
+    {
+        let database_name = {
+            // Get a key to access the metadata store.
+            let key = Self::key()?;
+            // Get metadata store.
+            let metadata = METADATA.lock().await;
+            // Get metadata of Model.
+            if let Some(meta) = metadata.get(&key) {
+                meta.database_name.clone()
+            } else {
+                Err(format!(
+                    "Model key: `{key}` ; Method: `open_media_stream()` => \
+                    Failed to get data from cache.",
+                ))?
+            }
+        };
+        let storage = GridFsStorage::new(client, database_name.as_str(), GRIDFS_BUCKET, "");
+        storage.open_download_stream(path).await
+    }
+
     /// Gets the name of the Collection.
     /// https://docs.rs/mongodb/latest/mongodb/struct.Collection.html#method.name
     ///
@@ -2,36 +2,98 @@
 
 use async_trait::async_trait;
 use chrono::{format::ParseErrorKind, DateTime, Utc};
-use image::imageops::FilterType::{Nearest, Triangle};
 use mongodb::{
-    bson::{doc, oid::ObjectId, ser::to_bson, spec::ElementType, Bson, Document},
-    options::{DeleteOptions, FindOneOptions, InsertOneOptions, UpdateOptions},
+    bson::{
+        de::from_document,
+        doc,
+        ser::{to_bson, to_document},
+        spec::{BinarySubtype, ElementType},
+        Binary, Bson, Document,
+    },
+    options::{
+        DeleteOptions, FindOneAndUpdateOptions, FindOneOptions, InsertOneOptions, ReturnDocument,
+        UpdateOptions,
+    },
     results::InsertOneResult,
     Client, Collection,
 };
-use rand::Rng;
 use regex::Regex;
 use serde::{de::DeserializeOwned, ser::Serialize};
 use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use slug::slugify;
-use std::{convert::TryFrom, error::Error, fs, fs::Metadata, path::Path};
+use std::{convert::TryFrom, error::Error, fs, path::Path, sync::Arc};
 use uuid::Uuid;
 
 use crate::{
     models::{
         addition::Addition,
         caching::Caching,
-        helpers::{FileData, ImageData},
+        helpers::{FileData, IdType, ImageData},
         hooks::Hooks,
+        imaging::{
+            calculate_thumbnail_size, exif_orientation, image_dimensions, process_image,
+            ImageOptions,
+        },
         output_data::{OutputData, OutputData2},
-        validation::Validation,
+        password::{hash_password, verify_password_hash},
+        validation::{check_password_strength, read_file_head, Validation},
         Main,
     },
+    storage::{
+        get_storage, media_refs_coll, sha256_hex, GridFsStorage, Storage, GRIDFS_BUCKET,
+        GRIDFS_STORAGE,
+    },
     store::METADATA,
 };
 
 #[async_trait(?Send)]
 pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
+    /// Get the document identifier `_id` from the `hash` field,
+    /// according to the model parameter `id_type`.
+    // *********************************************************************************************
+    async fn doc_id(&self) -> Result<Option<Bson>, Box<dyn Error>> {
+        let id_type = {
+            // Get a key to access the metadata store.
+            let key = Self::key()?;
+            // Get metadata store.
+            let metadata = METADATA.lock().await;
+            // Get metadata of Model.
+            if let Some(meta) = metadata.get(&key) {
+                meta.id_type.clone()
+            } else {
+                Err(format!(
+                    "Model key: `{key}` ; Method: `doc_id()` => \
+                    Failed to get data from cache.",
+                ))?
+            }
+        };
+        let hash = self.hash();
+        if hash.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(id_type.to_bson(hash.as_str())?))
+    }
+
+    /// Determines the mode of accessing the database (create or update).
+    /// Hint: For identifiers supplied by the caller, the presence of the document is checked.
+    // *********************************************************************************************
+    async fn is_update(
+        &self,
+        id_type: &IdType,
+        coll: &Collection<Document>,
+    ) -> Result<bool, Box<dyn Error>> {
+        let hash = self.hash();
+        if hash.is_empty() {
+            return Ok(false);
+        }
+        if id_type.is_auto() {
+            return Ok(true);
+        }
+        let filter = doc! {"_id": id_type.to_bson(hash.as_str())?};
+        Ok(coll.count_documents(filter, None).await? > 0)
+    }
+
     /// Deleting a file in the database and in the file system.
     // *********************************************************************************************
     async fn delete_file(
@@ -43,10 +105,8 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
         image_default: Option<ImageData>,
     ) -> Result<(), Box<dyn Error>> {
         //
-        let hash = self.hash();
-        if !hash.is_empty() {
-            let object_id = ObjectId::parse_str(hash.as_str())?;
-            let filter = doc! {"_id": object_id};
+        if let Some(doc_id) = self.doc_id().await? {
+            let filter = doc! {"_id": doc_id};
             if let Some(document) = coll.find_one(filter.clone(), None).await? {
                 // If `is_deleted=true` was passed incorrectly.
                 if document.is_null(field_name) {
@@ -56,24 +116,35 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                 let file_doc = doc! {field_name: Bson::Null};
                 let update = doc! { "$set": file_doc };
                 coll.update_one(filter, update, None).await?;
+                // Get the storage backend of the field.
+                let (storage, is_content_addressed) = {
+                    let model_json = self.self_to_json_val()?;
+                    let field = model_json.get(field_name).unwrap();
+                    (
+                        Self::field_storage(field, coll).await?,
+                        field["is_content_addressed"].as_bool().unwrap(),
+                    )
+                };
                 // Delete the orphaned file.
                 if let Some(info_file) = document.get(field_name).unwrap().as_document() {
                     if let Some(file_default) = file_default {
                         let path_default = file_default.path;
                         let path = info_file.get_str("path")?;
-                        if path != path_default {
-                            let path = Path::new(path);
-                            if path.is_file() {
-                                fs::remove_file(path)?;
-                            }
+                        if path != path_default
+                            && (!is_content_addressed
+                                || Self::media_ref_release(coll, path).await?)
+                        {
+                            storage.delete(path).await?;
                         }
                     } else if let Some(image_default) = image_default {
                         let path_default = image_default.path;
-                        let path = info_file.get_str("path")?;
-                        if path != path_default {
-                            let dir_path = Path::new(path).parent().unwrap();
-                            if dir_path.is_dir() {
-                                fs::remove_dir_all(dir_path)?;
+                        let image_data = from_document::<ImageData>(info_file.clone())?;
+                        if image_data.path != path_default
+                            && (!is_content_addressed
+                                || Self::media_ref_release(coll, &image_data.path).await?)
+                        {
+                            for path in image_data.paths() {
+                                storage.delete(path).await?;
                             }
                         }
                     }
@@ -102,10 +173,8 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
         field_name: &str,
     ) -> Result<Value, Box<dyn Error>> {
         //
-        let hash = self.hash();
-        if !hash.is_empty() {
-            let object_id = ObjectId::parse_str(hash.as_str())?;
-            let filter = doc! {"_id": object_id};
+        if let Some(doc_id) = self.doc_id().await? {
+            let filter = doc! {"_id": doc_id};
             if let Some(document) = coll.find_one(filter, None).await? {
                 if let Some(doc) = document.get(field_name).unwrap().as_document() {
                     let result = serde_json::to_value(doc)?;
@@ -120,14 +189,224 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
     /// Calculate the maximum size for a thumbnail.
     // *********************************************************************************************
     fn calculate_thumbnail_size(width: f64, height: f64, max_size: f64) -> (f64, f64) {
-        if width > height {
-            if width > max_size {
-                return (max_size, (height * (max_size / width)).floor());
+        calculate_thumbnail_size(width, height, max_size)
+    }
+
+    /// Get the storage backend of the field ( FileField or ImageField ).
+    // *********************************************************************************************
+    async fn field_storage(
+        field: &Value,
+        coll: &Collection<Document>,
+    ) -> Result<Arc<dyn Storage + Send + Sync>, Box<dyn Error>> {
+        let storage_name = field["storage"].as_str().unwrap_or_default();
+        let media_url = field["media_url"].as_str().unwrap();
+        // GridFS bucket in the database of the Model.
+        if storage_name == GRIDFS_STORAGE {
+            return Ok(Arc::new(GridFsStorage::new(
+                coll.client(),
+                coll.namespace().db.as_str(),
+                GRIDFS_BUCKET,
+                media_url,
+            )));
+        }
+        get_storage(
+            storage_name,
+            field["media_root"].as_str().unwrap(),
+            media_url,
+        )
+        .await
+    }
+
+    /// Content-addressed storage - get a stored file with the same content.
+    /// Returns the file information ( FileData or ImageData ) or None if there is no such file.
+    /// Hint: The reference is added only after the document is written ( `media_refs_commit()` ).
+    // *********************************************************************************************
+    async fn media_ref_find(
+        coll: &Collection<Document>,
+        content_key: &str,
+    ) -> Result<Option<Document>, Box<dyn Error>> {
+        let refs_coll = media_refs_coll(coll);
+        if let Some(doc) = refs_coll.find_one(doc! {"_id": content_key}, None).await? {
+            return Ok(Some(doc.get_document("data")?.clone()));
+        }
+        Ok(None)
+    }
+
+    /// Content-addressed storage - register a new stored file without references.
+    /// Returns the file information of the registered file
+    /// ( it differs from `data` if the same content was registered at the same moment ).
+    // *********************************************************************************************
+    async fn media_ref_register(
+        coll: &Collection<Document>,
+        content_key: &str,
+        data: Document,
+    ) -> Result<Document, Box<dyn Error>> {
+        let refs_coll = media_refs_coll(coll);
+        let options = FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .build();
+        if let Some(doc) = refs_coll
+            .find_one_and_update(
+                doc! {"_id": content_key},
+                doc! {"$setOnInsert": {"data": data, "count": 0_i64}},
+                options,
+            )
+            .await?
+        {
+            return Ok(doc.get_document("data")?.clone());
+        }
+        Err(format!(
+            "Content key: `{content_key}` ; Method: `media_ref_register()` => \
+            Failed to register the file."
+        ))?
+    }
+
+    /// Content-addressed storage - add a reference to a stored file.
+    // *********************************************************************************************
+    async fn media_ref_acquire(
+        coll: &Collection<Document>,
+        path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let refs_coll = media_refs_coll(coll);
+        refs_coll
+            .update_one(
+                doc! {"data.path": path},
+                doc! {"$inc": {"count": 1_i64}},
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Content-addressed storage - remove a reference to a stored file.
+    /// Returns true if it was the last reference ( the file can be deleted ).
+    // *********************************************************************************************
+    async fn media_ref_release(
+        coll: &Collection<Document>,
+        path: &str,
+    ) -> Result<bool, Box<dyn Error>> {
+        let refs_coll = media_refs_coll(coll);
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        if let Some(doc) = refs_coll
+            .find_one_and_update(
+                doc! {"data.path": path},
+                doc! {"$inc": {"count": -1_i64}},
+                options,
+            )
+            .await?
+        {
+            if doc.get_i64("count")? > 0 {
+                return Ok(false);
+            }
+            // If a new reference was added at the same moment, the file is not deleted.
+            let filter = doc! {"_id": doc.get("_id").unwrap(), "count": {"$lte": 0_i64}};
+            let result = refs_coll.delete_one(filter, None).await?;
+            return Ok(result.deleted_count == 1);
+        }
+        // There is no reference counter.
+        Ok(true)
+    }
+
+    /// Content-addressed storage - remove a stored file registration that has no references.
+    /// Returns true if the registration is removed ( the file can be deleted ).
+    // *********************************************************************************************
+    async fn media_ref_discard(
+        coll: &Collection<Document>,
+        path: &str,
+    ) -> Result<bool, Box<dyn Error>> {
+        let refs_coll = media_refs_coll(coll);
+        let filter = doc! {"data.path": path, "count": {"$lte": 0_i64}};
+        let result = refs_coll.delete_one(filter, None).await?;
+        Ok(result.deleted_count == 1)
+    }
+
+    /// Get FileField and ImageField fields with new stored files in the document.
+    /// Hint: `check()` adds these fields to the document only for new stored files.
+    // *********************************************************************************************
+    fn new_media_fields(&self, doc: &Document) -> Result<Vec<(String, Value)>, Box<dyn Error>> {
+        let model_json = self.self_to_json_val()?;
+        let mut fields = Vec::new();
+        for (field_name, value) in doc.iter() {
+            if let (Some(field), Some(_)) = (model_json.get(field_name), value.as_document()) {
+                let field_type = field["field_type"].as_str().unwrap_or_default();
+                if field_type == "FileField" || field_type == "ImageField" {
+                    fields.push((field_name.clone(), field.clone()));
+                }
             }
-        } else if height > max_size {
-            return ((width * (max_size / height)).floor(), max_size);
         }
-        (0.0, 0.0)
+        Ok(fields)
+    }
+
+    /// Delete a stored file ( FileData ) or image with thumbnails ( ImageData ).
+    // *********************************************************************************************
+    async fn delete_stored_media(
+        field: &Value,
+        coll: &Collection<Document>,
+        info_file: &Document,
+    ) -> Result<(), Box<dyn Error>> {
+        let storage = Self::field_storage(field, coll).await?;
+        if field["field_type"].as_str() == Some("ImageField") {
+            let image_data = from_document::<ImageData>(info_file.clone())?;
+            for path in image_data.paths() {
+                storage.delete(path).await?;
+            }
+        } else {
+            storage.delete(info_file.get_str("path")?).await?;
+        }
+        Ok(())
+    }
+
+    /// Content-addressed storage - add references to the new stored files of the written document
+    /// and remove references to the replaced files.
+    /// Hint: Called after the document is successfully written.
+    // *********************************************************************************************
+    async fn media_refs_commit(
+        coll: &Collection<Document>,
+        new_doc: &Document,
+        old_doc: Option<&Document>,
+        fields: &[(String, Value)],
+    ) -> Result<(), Box<dyn Error>> {
+        for (field_name, field) in fields {
+            if !field["is_content_addressed"].as_bool().unwrap_or_default() {
+                continue;
+            }
+            let new_path = new_doc.get_document(field_name)?.get_str("path")?;
+            Self::media_ref_acquire(coll, new_path).await?;
+            // Delete the replaced file if it was the last reference.
+            if let Some(info_file) = old_doc.and_then(|doc| doc.get_document(field_name).ok()) {
+                let old_path = info_file.get_str("path")?;
+                let path_default = field["default"]["path"].as_str().unwrap_or_default();
+                if old_path != new_path
+                    && old_path != path_default
+                    && Self::media_ref_release(coll, old_path).await?
+                {
+                    Self::delete_stored_media(field, coll, info_file).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete the new stored files of a document that was not written.
+    /// Hint: Content-addressed files are deleted if they are not referenced by other documents.
+    // *********************************************************************************************
+    async fn media_refs_discard(
+        coll: &Collection<Document>,
+        doc: &Document,
+        fields: &[(String, Value)],
+    ) -> Result<(), Box<dyn Error>> {
+        for (field_name, field) in fields {
+            let info_file = doc.get_document(field_name)?;
+            if !field["is_content_addressed"].as_bool().unwrap_or_default()
+                || Self::media_ref_discard(coll, info_file.get_str("path")?).await?
+            {
+                Self::delete_stored_media(field, coll, info_file).await?;
+            }
+        }
+        Ok(())
     }
 
     /// Checking the Model before queries the database.
@@ -172,6 +451,8 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
             is_up_doc,
             app_name,
             unique_app_key,
+            id_type,
+            argon2_params,
         ) = {
             // Get a key to access the metadata store.
             let key = Self::key()?;
@@ -194,6 +475,8 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     meta.is_up_doc,
                     meta.app_name.clone(),
                     meta.unique_app_key.clone(),
+                    meta.id_type.clone(),
+                    meta.argon2_params(),
                 )
             } else {
                 Err(format!(
@@ -204,9 +487,8 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
         };
         // Get model name.
         let model_name = model_name.as_str();
-        // Determines the mode of accessing the database (create or update).
+        // Get hash-line of Model.
         let hash = &self.hash();
-        let is_update: bool = !hash.is_empty();
         // User input error detection symptom.
         let mut is_err_symptom = false;
         // To block the reuse of previously saved files and images in the media directory.
@@ -216,6 +498,22 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
         let coll = client
             .database(&database_name)
             .collection::<Document>(&collection_name);
+        // Determines the mode of accessing the database (create or update).
+        let is_update: bool = self.is_update(&id_type, &coll).await?;
+        // Document identifier `_id` ( for a new document with an identifier from the caller ).
+        let doc_id: Option<Bson> = if !hash.is_empty() {
+            Some(id_type.to_bson(hash)?)
+        } else {
+            None
+        };
+        // The identifier of a new document must be supplied by the caller.
+        if is_save && doc_id.is_none() && !id_type.is_auto() {
+            Err(format!(
+                "Model: `{model_name}` > Field: `hash` ; Method: `check()` => \
+                For the model parameter `id_type = {id_type:?}`, \
+                the document identifier must be set in the `hash` field."
+            ))?
+        }
         // Get preliminary data from model instance and use for final result.
         let mut final_model_json = self.self_to_json_val()?;
         // Document for the final result.
@@ -282,6 +580,13 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
             if field_name == "hash" {
                 continue;
             }
+            // Get the username value for the password strength policy.
+            let username = final_model_json[field_name]
+                .get("username_field")
+                .and_then(|val| val.as_str())
+                .filter(|val| !val.is_empty())
+                .and_then(|name| final_model_json[name]["value"].as_str())
+                .map(|val| val.to_string());
             // Get values for validation.
             let final_field = final_model_json.get_mut(field_name).unwrap();
             // Define conditional constants.
@@ -424,20 +729,25 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     if let Some(unique) = final_field.get("unique") {
                         let is_unique = unique.as_bool().unwrap();
                         if field_type != "PasswordField" && is_unique {
-                            Self::check_unique(hash, field_name, &field_value_bson, &coll)
-                                .await
-                                .unwrap_or_else(|err| {
-                                    is_err_symptom = true;
-                                    if !is_hide {
-                                        Self::accumula_err(final_field, &err.to_string());
-                                    } else {
-                                        panic!(
-                                            "Model: `{model_name}` > Field: `{field_name}` ; \
+                            Self::check_unique(
+                                doc_id.as_ref(),
+                                field_name,
+                                &field_value_bson,
+                                &coll,
+                            )
+                            .await
+                            .unwrap_or_else(|err| {
+                                is_err_symptom = true;
+                                if !is_hide {
+                                    Self::accumula_err(final_field, &err.to_string());
+                                } else {
+                                    panic!(
+                                        "Model: `{model_name}` > Field: `{field_name}` ; \
                                             Method: `check()` => {0:?}",
-                                            err
-                                        )
-                                    }
-                                });
+                                        err
+                                    )
+                                }
+                            });
                         }
                     }
                     // Validation Email, Url, IP, IPv4, IPv6, Color.
@@ -453,6 +763,17 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                             )
                         }
                     });
+                    // Validation of password strength.
+                    if field_type == "PasswordField" {
+                        let err_list =
+                            check_password_strength(curr_val, final_field, username.as_deref());
+                        if !err_list.is_empty() {
+                            is_err_symptom = true;
+                            for err in err_list {
+                                Self::accumula_err(final_field, &err);
+                            }
+                        }
+                    }
                     // Insert result.
                     if is_save && !is_err_symptom && !ignore_fields.contains(field_name) {
                         match field_type {
@@ -460,7 +781,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                                 if !curr_val.is_empty() && !is_update {
                                     // Generate password hash and add to result document.
                                     let password_hash: String =
-                                        Self::create_password_hash(curr_val)?;
+                                        hash_password(curr_val, &argon2_params)?;
                                     final_doc.insert(field_name, Bson::String(password_hash));
                                 }
                             }
@@ -524,7 +845,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     let field_value_bson = Bson::String(slug.clone());
                     // Validation of `unique`.
                     if final_field.get("unique").unwrap().as_bool().unwrap() {
-                        Self::check_unique(hash, field_name, &field_value_bson, &coll)
+                        Self::check_unique(doc_id.as_ref(), field_name, &field_value_bson, &coll)
                             .await
                             .unwrap_or_else(|err| {
                                 is_err_symptom = true;
@@ -695,7 +1016,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     let val_dt_bson = Bson::DateTime(curr_dt.into());
                     // Validation of `unique`
                     if final_field["unique"].as_bool().unwrap() {
-                        Self::check_unique(hash, field_name, &val_dt_bson, &coll)
+                        Self::check_unique(doc_id.as_ref(), field_name, &val_dt_bson, &coll)
                             .await
                             .unwrap_or_else(|err| {
                                 is_err_symptom = true;
@@ -709,7 +1030,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                 }
                 // Validation of `choice` type fields.
                 // *********************************************************************************
-                // "ChoiceTextField" | "ChoiceI32Field" | "ChoiceU32Field"
+                // "ChoiceTextField" | "ChoiceEnumField" | "ChoiceI32Field" | "ChoiceU32Field"
                 // | "ChoiceI64Field" | "ChoiceF64Field"
                 4 => {
                     //
@@ -729,7 +1050,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     }
                     // Get selected items.
                     match field_type {
-                        "ChoiceTextField" => {
+                        "ChoiceTextField" | "ChoiceEnumField" => {
                             let val = const_value.as_str().unwrap().to_string();
                             let mut flag = true;
                             if choice_str_map.get(field_name).unwrap().contains(&val) {
@@ -1206,7 +1527,11 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                         }
                     }
                     //
-                    if is_slug_update || regex_is_dated_path.is_match(file_data.path.as_str()) {
+                    // ( GridFS stores the file identifier in the `path` field )
+                    if is_slug_update
+                        || regex_is_dated_path.is_match(file_data.path.as_str())
+                        || curr_file_info["path"].as_str() == Some(file_data.path.as_str())
+                    {
                         *final_field.get_mut("value").unwrap() = curr_file_info;
                         continue;
                     }
@@ -1219,7 +1544,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                         ))?
                     }
                     // Create path for validation of file.
-                    let source_file_path = Path::new(file_data.path.as_str());
+                    let source_file_path = &Path::new(file_data.path.as_str()).to_path_buf();
                     if !source_file_path.is_file() {
                         Err(format!(
                             "Model: `{model_name}` > Field: `{field_name}` ; Method: \
@@ -1227,47 +1552,84 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                             file_data.path
                         ))?
                     }
-                    // Create a new path and URL for the file.
+                    // Validation of file size and content type.
+                    // ( The file is read into memory only after the checks )
+                    if let Err(err) = Self::check_file_content(
+                        &read_file_head(source_file_path)?,
+                        fs::metadata(source_file_path)?.len(),
+                        final_field["max_size"].as_u64().unwrap(),
+                        &serde_json::from_value::<Vec<String>>(
+                            final_field["allowed_types"].clone(),
+                        )?,
+                        false,
+                    ) {
+                        is_err_symptom = true;
+                        Self::accumula_err(final_field, &err.to_string());
+                        continue;
+                    }
+                    let data = fs::read(source_file_path)?;
+                    file_data.checksum = sha256_hex(&data);
+                    // Save the file to storage and create a new path and URL for the file.
                     {
-                        let media_root = final_field["media_root"].as_str().unwrap();
-                        let media_url = final_field["media_url"].as_str().unwrap();
                         let target_dir = final_field["target_dir"].as_str().unwrap();
-                        let date_arr = format!("{}", Utc::now().format("%Y-%m-%d"))
-                            .split('-')
-                            .map(|item| item.to_string())
-                            .collect::<Vec<String>>();
-                        let date_path =
-                            format!("{}/{}/{}-barrel", date_arr[0], date_arr[1], date_arr[2]);
-                        let file_dir_path = format!("{media_root}/{target_dir}/{date_path}");
-                        let extension = {
-                            let path = Path::new(file_data.path.as_str());
-                            path.extension().unwrap().to_str().unwrap()
+                        let extension = source_file_path.extension().unwrap().to_str().unwrap();
+                        let is_content_addressed =
+                            final_field["is_content_addressed"].as_bool().unwrap();
+                        let (new_file_name, key) = if is_content_addressed {
+                            // Content-addressed storage.
+                            let checksum = file_data.checksum.as_str();
+                            let new_file_name = format!("{checksum}.{extension}");
+                            let key =
+                                format!("{target_dir}/sha256/{}/{new_file_name}", &checksum[..2]);
+                            (new_file_name, key)
+                        } else {
+                            let date_arr = format!("{}", Utc::now().format("%Y-%m-%d"))
+                                .split('-')
+                                .map(|item| item.to_string())
+                                .collect::<Vec<String>>();
+                            let date_path =
+                                format!("{}/{}/{}-barrel", date_arr[0], date_arr[1], date_arr[2]);
+                            let new_file_name = format!("{}.{extension}", Uuid::new_v4());
+                            let key = format!("{target_dir}/{date_path}/{new_file_name}");
+                            (new_file_name, key)
+                        };
+                        // Get a stored file with the same content.
+                        let stored_file = if is_content_addressed {
+                            Self::media_ref_find(&coll, key.as_str()).await?
+                        } else {
+                            None
                         };
-                        let mut new_file_name;
-                        let mut new_file_path;
-                        fs::create_dir_all(file_dir_path.clone())?;
-                        loop {
-                            new_file_name = format!("{}.{extension}", Uuid::new_v4());
-                            new_file_path = format!("{file_dir_path}/{new_file_name}");
-                            if !Path::new(&new_file_path).is_file() {
-                                break;
+                        if let Some(stored_file) = stored_file {
+                            file_data = from_document::<FileData>(stored_file)?;
+                        } else {
+                            let storage = Self::field_storage(final_field, &coll).await?;
+                            // Get file size in bytes.
+                            file_data.size = data.len() as f64;
+                            let new_file_path = storage.put(key.as_str(), data).await?;
+                            file_data.name = new_file_name;
+                            file_data.url = storage.url(new_file_path.as_str());
+                            file_data.path = new_file_path;
+                            // Register the new content-addressed file.
+                            if is_content_addressed {
+                                let stored_file = from_document::<FileData>(
+                                    Self::media_ref_register(
+                                        &coll,
+                                        key.as_str(),
+                                        to_document(&file_data)?,
+                                    )
+                                    .await?,
+                                )?;
+                                // The same content was stored at the same moment.
+                                if stored_file.path != file_data.path {
+                                    storage.delete(file_data.path.as_str()).await?;
+                                    file_data = stored_file;
+                                }
                             }
                         }
-                        fs::copy(source_file_path, new_file_path.clone())?;
                         if !is_use_default {
                             fs::remove_file(source_file_path)?;
                         }
-                        file_data.name = new_file_name.clone();
-                        file_data.path = new_file_path;
-                        file_data.url =
-                            format!("{media_url}/{target_dir}/{date_path}/{new_file_name}");
                     }
-                    //
-                    let f_path = Path::new(file_data.path.as_str());
-                    // Get file metadata.
-                    let metadata: Metadata = f_path.metadata()?;
-                    // Get file size in bytes.
-                    file_data.size = metadata.len() as f64;
                     // Insert result.
                     if !ignore_fields.contains(field_name) {
                         // Add file data to controller.
@@ -1347,7 +1709,11 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                         }
                     }
                     //
-                    if is_slug_update || regex_is_dated_path.is_match(image_data.path.as_str()) {
+                    // ( GridFS stores the file identifier in the `path` field )
+                    if is_slug_update
+                        || regex_is_dated_path.is_match(image_data.path.as_str())
+                        || curr_file_info["path"].as_str() == Some(image_data.path.as_str())
+                    {
                         *final_field.get_mut("value").unwrap() = curr_file_info;
                         continue;
                     }
@@ -1360,7 +1726,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                         ))?
                     }
                     // Validation of file.
-                    let source_img_path = Path::new(image_data.path.as_str());
+                    let source_img_path = &Path::new(image_data.path.as_str()).to_path_buf();
                     if !source_img_path.is_file() {
                         Err(format!(
                             "Model: `{model_name}` > Field: `{field_name}` ; Method: \
@@ -1368,109 +1734,177 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                             image_data.path
                         ))?
                     }
-                    // Create a new path and URL for the image.
-                    let extension = source_img_path
-                        .extension()
-                        .unwrap()
-                        .to_str()
-                        .unwrap()
-                        .to_string();
-                    let mut img_dir_path;
-                    let img_dir_url;
-                    {
-                        let media_root = final_field["media_root"].as_str().unwrap();
-                        let media_url = final_field["media_url"].as_str().unwrap();
-                        let target_dir = final_field["target_dir"].as_str().unwrap();
-                        let date_arr = format!("{}", Utc::now().format("%Y-%m-%d"))
-                            .split('-')
-                            .map(|item| item.to_string())
-                            .collect::<Vec<String>>();
-                        let date_path =
-                            format!("{}/{}/{}-barrel", date_arr[0], date_arr[1], date_arr[2]);
-                        let new_img_name = format!("main.{extension}");
-                        let mut uuid;
-                        loop {
-                            uuid = Uuid::new_v4().to_string();
-                            img_dir_path = format!("{media_root}/{target_dir}/{date_path}/{uuid}");
-                            if !Path::new(&img_dir_path).is_dir() {
-                                fs::create_dir_all(img_dir_path.clone())?;
-                                break;
-                            }
+                    // Validation of image size and content type.
+                    // ( The file is read into memory only after the checks )
+                    if let Err(err) = Self::check_file_content(
+                        &read_file_head(source_img_path)?,
+                        fs::metadata(source_img_path)?.len(),
+                        final_field["max_size"].as_u64().unwrap(),
+                        &serde_json::from_value::<Vec<String>>(
+                            final_field["allowed_types"].clone(),
+                        )?,
+                        true,
+                    ) {
+                        is_err_symptom = true;
+                        Self::accumula_err(final_field, &err.to_string());
+                        continue;
+                    }
+                    let data = fs::read(source_img_path)?;
+                    // Get image processing options.
+                    let options = ImageOptions::from_field(final_field).map_err(|err| {
+                        format!(
+                            "Model: `{model_name}` > Field: `{field_name}` ; \
+                            Method: `check()` => {err}"
+                        )
+                    })?;
+                    let orientation = if options.is_auto_orient {
+                        exif_orientation(&data)
+                    } else {
+                        1
+                    };
+                    // Get image width and height ( without decoding ).
+                    let dimensions = image_dimensions(&data, orientation)?;
+                    // Validation of image dimensions.
+                    // ( Protection against decompression bombs - `max_width` and `max_height` )
+                    let err_list = Self::check_image_dimensions(dimensions, final_field);
+                    if !err_list.is_empty() {
+                        is_err_symptom = true;
+                        for err in err_list {
+                            Self::accumula_err(final_field, &err);
                         }
-                        let new_img_path = format!("{img_dir_path}/{new_img_name}");
-                        fs::copy(source_img_path, new_img_path.clone())?;
-                        if !is_use_default {
-                            fs::remove_file(source_img_path)?;
-                        }
-                        image_data.name = new_img_name.clone();
-                        image_data.path = new_img_path;
-                        img_dir_url = format!("{media_url}/{target_dir}/{date_path}/{uuid}");
-                        image_data.url = format!("{img_dir_url}/{new_img_name}");
-                    }
-                    // Get image path.
-                    let f_path = Path::new(image_data.path.as_str());
-                    // Get file metadata.
-                    let metadata: Metadata = f_path.metadata()?;
-                    // Get file size in bytes.
-                    image_data.size = metadata.len() as f64;
-                    // Get image width and height.
-                    let dimensions = image::image_dimensions(f_path)?;
+                        continue;
+                    }
                     image_data.width = dimensions.0 as f64;
                     image_data.height = dimensions.1 as f64;
-                    // Create thumbnails.
+                    let output_format = options.output_format(image::guess_format(&data)?);
+                    let extension = if options.output_format.is_empty() {
+                        source_img_path
+                            .extension()
+                            .unwrap()
+                            .to_str()
+                            .unwrap()
+                            .to_string()
+                    } else {
+                        output_format.extensions_str()[0].to_string()
+                    };
                     let thumbnails = serde_json::from_value::<Vec<(String, u32)>>(
                         final_field.get("thumbnails").unwrap().clone(),
                     )?;
-                    let filter_type = if final_field.get("is_quality").unwrap().as_bool().unwrap() {
-                        Triangle
+                    // Content-addressed storage - key of the image directory.
+                    // ( Hash of the uploaded image and processing options )
+                    let content_key = if final_field["is_content_addressed"].as_bool().unwrap() {
+                        let target_dir = final_field["target_dir"].as_str().unwrap();
+                        let mut hasher = Sha256::new();
+                        hasher.update(&data);
+                        hasher.update(serde_json::to_string(&json!({
+                            "is_quality": options.is_quality,
+                            "is_auto_orient": options.is_auto_orient,
+                            "is_strip_metadata": options.is_strip_metadata,
+                            "output_format": options.output_format,
+                            "jpeg_quality": options.jpeg_quality,
+                            "resize_mode": options.resize_mode,
+                            "aspect_ratio": options.aspect_ratio,
+                            "pad_color": options.pad_color,
+                            "is_placeholder": options.is_placeholder,
+                            "thumbnails": thumbnails,
+                        }))?);
+                        let hash = format!("{:x}", hasher.finalize());
+                        Some(format!("{target_dir}/sha256/{}/{hash}", &hash[..2]))
                     } else {
-                        Nearest
+                        None
                     };
-                    if !thumbnails.is_empty() {
-                        let mut img = image::open(f_path)?;
-                        for max_size in thumbnails.iter() {
-                            let thumbnail_size: (f64, f64) = Self::calculate_thumbnail_size(
-                                image_data.width,
-                                image_data.height,
-                                max_size.1 as f64,
-                            );
-                            if thumbnail_size.0 > 0.0 && thumbnail_size.1 > 0.0 {
-                                let width = thumbnail_size.0;
-                                let height = thumbnail_size.1;
-                                let thumb_name = format!("{}.{extension}", max_size.0);
-                                let thumb_path = format!("{img_dir_path}/{thumb_name}");
-                                let thumb_url = format!("{img_dir_url}/{thumb_name}");
-                                img = img.resize_exact(width as u32, height as u32, filter_type);
-                                match max_size.0.as_str() {
-                                    "lg" => {
-                                        img.save(thumb_path.clone())?;
-                                        image_data.path_lg = thumb_path;
-                                        image_data.url_lg = thumb_url;
-                                    }
-                                    "md" => {
-                                        img.save(thumb_path.clone())?;
-                                        image_data.path_md = thumb_path;
-                                        image_data.url_md = thumb_url;
-                                    }
-                                    "sm" => {
-                                        img.save(thumb_path.clone())?;
-                                        image_data.path_sm = thumb_path;
-                                        image_data.url_sm = thumb_url;
-                                    }
-                                    "xs" => {
-                                        img.save(thumb_path.clone())?;
-                                        image_data.path_xs = thumb_path;
-                                        image_data.url_xs = thumb_url;
-                                    }
-                                    _ => Err(format!(
-                                        "Model: `{model_name}` > Field: `{field_name}` > \
-                                            Type: `ImageData` ; Method: `check()` => \
-                                            Valid size names - `xs`, `sm`, `md`, `lg`."
-                                    ))?,
+                    // Get a stored image with the same content.
+                    let stored_image = if let Some(content_key) = content_key.as_ref() {
+                        Self::media_ref_find(&coll, content_key).await?
+                    } else {
+                        None
+                    };
+                    if let Some(stored_image) = stored_image {
+                        image_data = from_document::<ImageData>(stored_image)?;
+                    } else {
+                        // Decoding, re-encoding and creating thumbnails ( in a blocking thread pool ).
+                        let processed_image =
+                            process_image(data, orientation, output_format, &options, &thumbnails)
+                                .await?;
+                        // Create a new path and URL for the image.
+                        let storage = Self::field_storage(final_field, &coll).await?;
+                        let img_dir_key;
+                        {
+                            let target_dir = final_field["target_dir"].as_str().unwrap();
+                            let date_arr = format!("{}", Utc::now().format("%Y-%m-%d"))
+                                .split('-')
+                                .map(|item| item.to_string())
+                                .collect::<Vec<String>>();
+                            let date_path =
+                                format!("{}/{}/{}-barrel", date_arr[0], date_arr[1], date_arr[2]);
+                            let new_img_name = format!("main.{extension}");
+                            img_dir_key = content_key.clone().unwrap_or_else(|| {
+                                format!("{target_dir}/{date_path}/{}", Uuid::new_v4())
+                            });
+                            let key = format!("{img_dir_key}/{new_img_name}");
+                            // Get file size in bytes.
+                            image_data.size = processed_image.data.len() as f64;
+                            image_data.checksum = sha256_hex(&processed_image.data);
+                            let new_img_path =
+                                storage.put(key.as_str(), processed_image.data).await?;
+                            image_data.name = new_img_name;
+                            image_data.url = storage.url(new_img_path.as_str());
+                            image_data.path = new_img_path;
+                        }
+                        image_data.blurhash = processed_image.blurhash;
+                        image_data.dominant_color = processed_image.dominant_color;
+                        // Save thumbnails.
+                        for (size_name, thumb_data) in processed_image.thumbnails {
+                            let thumb_key = format!("{img_dir_key}/{size_name}.{extension}");
+                            let thumb_path = storage.put(thumb_key.as_str(), thumb_data).await?;
+                            let thumb_url = storage.url(thumb_path.as_str());
+                            match size_name.as_str() {
+                                "lg" => {
+                                    image_data.path_lg = thumb_path;
+                                    image_data.url_lg = thumb_url;
                                 }
-                            };
+                                "md" => {
+                                    image_data.path_md = thumb_path;
+                                    image_data.url_md = thumb_url;
+                                }
+                                "sm" => {
+                                    image_data.path_sm = thumb_path;
+                                    image_data.url_sm = thumb_url;
+                                }
+                                "xs" => {
+                                    image_data.path_xs = thumb_path;
+                                    image_data.url_xs = thumb_url;
+                                }
+                                _ => Err(format!(
+                                    "Model: `{model_name}` > Field: `{field_name}` > \
+                                        Type: `ImageData` ; Method: `check()` => \
+                                        Valid size names - `xs`, `sm`, `md`, `lg`."
+                                ))?,
+                            }
+                        }
+                        // Register the new content-addressed image.
+                        if let Some(content_key) = content_key.as_ref() {
+                            let stored_image = from_document::<ImageData>(
+                                Self::media_ref_register(
+                                    &coll,
+                                    content_key,
+                                    to_document(&image_data)?,
+                                )
+                                .await?,
+                            )?;
+                            // The same content was stored at the same moment.
+                            if stored_image.path != image_data.path {
+                                for path in image_data.paths() {
+                                    storage.delete(path).await?;
+                                }
+                                image_data = stored_image;
+                            }
                         }
                     }
+                    // Remove the uploaded source image.
+                    if !is_use_default {
+                        fs::remove_file(source_img_path)?;
+                    }
                     // Insert result.
                     if !ignore_fields.contains(field_name) {
                         // Add file data to controller.
@@ -1522,20 +1956,25 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     if let Some(unique) = unique {
                         let is_unique = unique.as_bool().unwrap();
                         if is_unique {
-                            Self::check_unique(hash, field_name, &field_value_bson, &coll)
-                                .await
-                                .unwrap_or_else(|err| {
-                                    is_err_symptom = true;
-                                    if !is_hide {
-                                        Self::accumula_err(final_field, &err.to_string());
-                                    } else {
-                                        panic!(
-                                            "Model: `{}` > Field: `{}` ; \
+                            Self::check_unique(
+                                doc_id.as_ref(),
+                                field_name,
+                                &field_value_bson,
+                                &coll,
+                            )
+                            .await
+                            .unwrap_or_else(|err| {
+                                is_err_symptom = true;
+                                if !is_hide {
+                                    Self::accumula_err(final_field, &err.to_string());
+                                } else {
+                                    panic!(
+                                        "Model: `{}` > Field: `{}` ; \
                                                 Method: `check()` => {}",
-                                            model_name, field_name, err
-                                        )
-                                    }
-                                });
+                                        model_name, field_name, err
+                                    )
+                                }
+                            });
                         }
                     }
                     // Compare with `min`.
@@ -1599,20 +2038,25 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     if let Some(unique) = unique {
                         let is_unique = unique.as_bool().unwrap();
                         if is_unique {
-                            Self::check_unique(hash, field_name, &field_value_bson, &coll)
-                                .await
-                                .unwrap_or_else(|err| {
-                                    is_err_symptom = true;
-                                    if !is_hide {
-                                        Self::accumula_err(final_field, &err.to_string());
-                                    } else {
-                                        panic!(
-                                            "Model: `{}` > Field: `{}` ; \
+                            Self::check_unique(
+                                doc_id.as_ref(),
+                                field_name,
+                                &field_value_bson,
+                                &coll,
+                            )
+                            .await
+                            .unwrap_or_else(|err| {
+                                is_err_symptom = true;
+                                if !is_hide {
+                                    Self::accumula_err(final_field, &err.to_string());
+                                } else {
+                                    panic!(
+                                        "Model: `{}` > Field: `{}` ; \
                                                 Method: `check()` => {}",
-                                            model_name, field_name, err
-                                        )
-                                    }
-                                });
+                                        model_name, field_name, err
+                                    )
+                                }
+                            });
                         }
                     }
                     // Compare with `min`.
@@ -1677,20 +2121,25 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     if let Some(unique) = unique {
                         let is_unique = unique.as_bool().unwrap();
                         if is_unique {
-                            Self::check_unique(hash, field_name, &field_value_bson, &coll)
-                                .await
-                                .unwrap_or_else(|err| {
-                                    is_err_symptom = true;
-                                    if !is_hide {
-                                        Self::accumula_err(final_field, &err.to_string());
-                                    } else {
-                                        panic!(
-                                            "Model: `{}` > Field: `{}` ; \
+                            Self::check_unique(
+                                doc_id.as_ref(),
+                                field_name,
+                                &field_value_bson,
+                                &coll,
+                            )
+                            .await
+                            .unwrap_or_else(|err| {
+                                is_err_symptom = true;
+                                if !is_hide {
+                                    Self::accumula_err(final_field, &err.to_string());
+                                } else {
+                                    panic!(
+                                        "Model: `{}` > Field: `{}` ; \
                                                 Method: `check()` => {}",
-                                            model_name, field_name, err
-                                        )
-                                    }
-                                });
+                                        model_name, field_name, err
+                                    )
+                                }
+                            });
                         }
                     }
                     // Compare with `min`.
@@ -1732,6 +2181,358 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                         final_doc.insert(field_name, field_value_bson);
                     }
                 }
+                // Validation of UUID type fields.
+                // *********************************************************************************
+                // "UUIDField"
+                14 => {
+                    // Generate a new value, if required.
+                    if const_value.is_null() && final_field["is_auto"].as_bool().unwrap() {
+                        const_value = json!(Uuid::new_v4().hyphenated().to_string());
+                        *final_field.get_mut("value").unwrap() = const_value.clone();
+                    }
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // ( The default value is used whenever possible )
+                    if const_value.is_null() {
+                        if is_required {
+                            is_err_symptom = true;
+                            Self::accumula_err(final_field, &t!("required_field"));
+                        }
+                        if is_save && !ignore_fields.contains(field_name) {
+                            final_doc.insert(field_name, Bson::Null);
+                        }
+                        continue;
+                    }
+                    //
+                    if is_save && is_use_default {
+                        *final_field.get_mut("value").unwrap() = const_value.clone();
+                    }
+                    // Get clean data.
+                    let curr_uuid = match Uuid::parse_str(const_value.as_str().unwrap()) {
+                        Ok(uuid) => uuid,
+                        Err(_) => {
+                            is_err_symptom = true;
+                            Self::accumula_err(final_field, &t!("invalid_uuid"));
+                            continue;
+                        }
+                    };
+                    *final_field.get_mut("value").unwrap() =
+                        json!(curr_uuid.hyphenated().to_string());
+                    // Used to validation uniqueness and in the final result.
+                    let field_value_bson = Bson::Binary(Binary {
+                        subtype: BinarySubtype::Uuid,
+                        bytes: curr_uuid.as_bytes().to_vec(),
+                    });
+                    // Validation of `unique`.
+                    if final_field["unique"].as_bool().unwrap() {
+                        Self::check_unique(doc_id.as_ref(), field_name, &field_value_bson, &coll)
+                            .await
+                            .unwrap_or_else(|err| {
+                                is_err_symptom = true;
+                                Self::accumula_err(final_field, &err.to_string());
+                            });
+                    }
+                    // Insert result.
+                    if is_save && !is_err_symptom && !ignore_fields.contains(field_name) {
+                        final_doc.insert(field_name, field_value_bson);
+                    }
+                }
+                // Validation of time type fields.
+                // *********************************************************************************
+                // "TimeField"
+                15 => {
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // ( The default value is used whenever possible )
+                    if const_value.is_null() {
+                        if is_required {
+                            is_err_symptom = true;
+                            Self::accumula_err(final_field, &t!("required_field"));
+                        }
+                        if is_save && !ignore_fields.contains(field_name) {
+                            final_doc.insert(field_name, Bson::Null);
+                        }
+                        continue;
+                    }
+                    //
+                    if is_save && is_use_default {
+                        *final_field.get_mut("value").unwrap() = const_value.clone();
+                    }
+                    // Create a Time object for the current value.
+                    let curr_time = match Self::parse_time(const_value.as_str().unwrap()) {
+                        Ok(time) => time,
+                        Err(_) => {
+                            is_err_symptom = true;
+                            Self::accumula_err(
+                                final_field,
+                                &format!(
+                                    "{} {} {}",
+                                    t!("incorrect_time"),
+                                    t!("format", sample = "hh:mm"),
+                                    t!("example", sample = "23:59")
+                                ),
+                            );
+                            continue;
+                        }
+                    };
+                    // Compare with `min`.
+                    let min = final_field["min"].as_str().unwrap();
+                    if !min.is_empty() {
+                        let min_time = Self::parse_time(min).map_err(|_| {
+                            format!(
+                                "Model: `{model_name}` > Field: `{field_name}` > \
+                                Param: `min` ; Method: `check()` => \
+                                Incorrect time format. Example: 23:59"
+                            )
+                        })?;
+                        if curr_time < min_time {
+                            is_err_symptom = true;
+                            Self::accumula_err(final_field, &t!("time_less_min"));
+                            continue;
+                        }
+                    }
+                    // Compare with `max`.
+                    let max = final_field["max"].as_str().unwrap();
+                    if !max.is_empty() {
+                        let max_time = Self::parse_time(max).map_err(|_| {
+                            format!(
+                                "Model: `{model_name}` > Field: `{field_name}` > \
+                                Param: `max` ; Method: `check()` => \
+                                Incorrect time format. Example: 23:59"
+                            )
+                        })?;
+                        if curr_time > max_time {
+                            is_err_symptom = true;
+                            Self::accumula_err(final_field, &t!("time_greater_max"));
+                            continue;
+                        }
+                    }
+                    // Used to validation uniqueness and in the final result.
+                    let time_text = curr_time.format("%H:%M:%S").to_string();
+                    *final_field.get_mut("value").unwrap() = json!(time_text);
+                    let field_value_bson = Bson::String(time_text);
+                    // Validation of `unique`.
+                    if final_field["unique"].as_bool().unwrap() {
+                        Self::check_unique(doc_id.as_ref(), field_name, &field_value_bson, &coll)
+                            .await
+                            .unwrap_or_else(|err| {
+                                is_err_symptom = true;
+                                Self::accumula_err(final_field, &err.to_string());
+                            });
+                    }
+                    // Insert result.
+                    if is_save && !is_err_symptom && !ignore_fields.contains(field_name) {
+                        final_doc.insert(field_name, field_value_bson);
+                    }
+                }
+                // Validation of list type fields.
+                // *********************************************************************************
+                // "ListTextField" | "ListI64Field" | "ListF64Field"
+                16 => {
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // ( The default value is used whenever possible )
+                    if const_value.is_null() {
+                        if is_required {
+                            is_err_symptom = true;
+                            Self::accumula_err(final_field, &t!("required_field"));
+                        }
+                        if is_save && !ignore_fields.contains(field_name) {
+                            final_doc.insert(field_name, Bson::Null);
+                        }
+                        continue;
+                    }
+                    //
+                    if is_save && is_use_default {
+                        *final_field.get_mut("value").unwrap() = const_value.clone();
+                    }
+                    // Get items.
+                    let mut items = const_value.as_array().unwrap().clone();
+                    // Remove duplicate items.
+                    if final_field["is_dedup"].as_bool().unwrap() {
+                        let mut tmp_items = Vec::<Value>::new();
+                        for item in items {
+                            if !tmp_items.contains(&item) {
+                                tmp_items.push(item);
+                            }
+                        }
+                        items = tmp_items;
+                        *final_field.get_mut("value").unwrap() = json!(items);
+                    }
+                    // A required list must contain at least one item.
+                    if items.is_empty() && is_required {
+                        is_err_symptom = true;
+                        Self::accumula_err(final_field, &t!("required_field"));
+                    }
+                    // Checking `min_items` and `max_items`.
+                    Self::check_items_count(
+                        final_field["min_items"].as_u64().unwrap() as usize,
+                        final_field["max_items"].as_u64().unwrap() as usize,
+                        items.len(),
+                    )
+                    .unwrap_or_else(|err| {
+                        is_err_symptom = true;
+                        Self::accumula_err(final_field, &err.to_string());
+                    });
+                    // Validation of items.
+                    let mut items_bson = Vec::<Bson>::new();
+                    for item in items.iter() {
+                        let item_bson = match field_type {
+                            "ListTextField" => {
+                                let curr_val = item.as_str().unwrap();
+                                // Validation field attribute `regex`.
+                                let pattern = final_field["regex"].as_str().unwrap();
+                                if Self::regex_validation(curr_val, pattern).is_err() {
+                                    is_err_symptom = true;
+                                    let regex_err_msg =
+                                        final_field["regex_err_msg"].as_str().unwrap().to_string();
+                                    Self::accumula_err(final_field, &regex_err_msg);
+                                }
+                                // Checking `minlength` and `maxlength`.
+                                let minlength = final_field["minlength"].as_u64().unwrap();
+                                let maxlength = final_field["maxlength"].as_u64().unwrap();
+                                Self::check_minlength(minlength as usize, curr_val)
+                                    .and_then(|_| {
+                                        Self::check_maxlength(maxlength as usize, curr_val)
+                                    })
+                                    .unwrap_or_else(|err| {
+                                        is_err_symptom = true;
+                                        Self::accumula_err(final_field, &err.to_string());
+                                    });
+                                Bson::String(curr_val.to_string())
+                            }
+                            "ListI64Field" => {
+                                let curr_val = item.as_i64().unwrap();
+                                // Compare with `min` and `max`.
+                                let min = final_field["min"].as_i64().unwrap();
+                                let max = final_field["max"].as_i64().unwrap();
+                                if curr_val < min {
+                                    is_err_symptom = true;
+                                    Self::accumula_err(
+                                        final_field,
+                                        &t!(
+                                            "number_not_less_min",
+                                            curr_num = curr_val,
+                                            min_num = min
+                                        ),
+                                    );
+                                }
+                                if curr_val > max {
+                                    is_err_symptom = true;
+                                    Self::accumula_err(
+                                        final_field,
+                                        &t!(
+                                            "number_not_greater_max",
+                                            curr_num = curr_val,
+                                            max_num = max
+                                        ),
+                                    );
+                                }
+                                Bson::Int64(curr_val)
+                            }
+                            "ListF64Field" => {
+                                let curr_val = item.as_f64().unwrap();
+                                // Compare with `min` and `max`.
+                                let min = final_field["min"].as_f64().unwrap();
+                                let max = final_field["max"].as_f64().unwrap();
+                                if curr_val < min {
+                                    is_err_symptom = true;
+                                    Self::accumula_err(
+                                        final_field,
+                                        &t!(
+                                            "number_not_less_min",
+                                            curr_num = curr_val,
+                                            min_num = min
+                                        ),
+                                    );
+                                }
+                                if curr_val > max {
+                                    is_err_symptom = true;
+                                    Self::accumula_err(
+                                        final_field,
+                                        &t!(
+                                            "number_not_greater_max",
+                                            curr_num = curr_val,
+                                            max_num = max
+                                        ),
+                                    );
+                                }
+                                Bson::Double(curr_val)
+                            }
+                            _ => Err(format!(
+                                "Model: `{model_name}` > Field: `{field_name}` ; \
+                                Method: `check()` => Unsupported field type - `{field_type}`."
+                            ))?,
+                        };
+                        // Validation of `unique` for each item.
+                        if final_field["unique"].as_bool().unwrap()
+                            && Self::check_unique(doc_id.as_ref(), field_name, &item_bson, &coll)
+                                .await
+                                .is_err()
+                        {
+                            is_err_symptom = true;
+                            let item_text = match item {
+                                Value::String(val) => val.clone(),
+                                val => val.to_string(),
+                            };
+                            Self::accumula_err(
+                                final_field,
+                                &t!("item_not_unique", item = item_text),
+                            );
+                        }
+                        items_bson.push(item_bson);
+                    }
+                    // Insert result.
+                    if is_save && !is_err_symptom && !ignore_fields.contains(field_name) {
+                        final_doc.insert(field_name, Bson::Array(items_bson));
+                    }
+                }
+                // Validation of JSON type fields.
+                // *********************************************************************************
+                // "JsonField"
+                17 => {
+                    // Validation, if the field is required and empty, accumulate the error.
+                    // ( The default value is used whenever possible )
+                    if const_value.is_null() {
+                        if is_required {
+                            is_err_symptom = true;
+                            Self::accumula_err(final_field, &t!("required_field"));
+                        }
+                        if is_save && !ignore_fields.contains(field_name) {
+                            final_doc.insert(field_name, Bson::Null);
+                        }
+                        continue;
+                    }
+                    //
+                    if is_save && is_use_default {
+                        *final_field.get_mut("value").unwrap() = const_value.clone();
+                    }
+                    // Only a JSON object or a JSON array is allowed.
+                    if !const_value.is_object() && !const_value.is_array() {
+                        is_err_symptom = true;
+                        Self::accumula_err(final_field, &t!("invalid_json"));
+                        continue;
+                    }
+                    // Validation field attribute `json_schema`.
+                    let json_schema = final_field["json_schema"].as_str().unwrap().to_string();
+                    if !json_schema.is_empty() {
+                        let err_list = Self::json_schema_validation(&const_value, &json_schema)
+                            .map_err(|err| {
+                                format!(
+                                    "Model: `{model_name}` > Field: `{field_name}` > \
+                                    Param: `json_schema` ; Method: `check()` => {err}"
+                                )
+                            })?;
+                        for err in err_list {
+                            is_err_symptom = true;
+                            Self::accumula_err(
+                                final_field,
+                                &format!("{} {}", t!("not_match_json_schema"), err),
+                            );
+                        }
+                    }
+                    // Insert result.
+                    if is_save && !is_err_symptom && !ignore_fields.contains(field_name) {
+                        final_doc.insert(field_name, to_bson(&const_value)?);
+                    }
+                }
                 _ => Err(format!(
                     "Model: `{model_name}` > Field: `{field_name}` ; \
                     Method: `check()` => Unsupported field type - `{field_type}`."
@@ -1747,8 +2548,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
             if is_update {
                 // Get the `created_at` value from the database.
                 let doc = {
-                    let object_id = ObjectId::parse_str(hash)?;
-                    let filter = doc! {"_id": object_id};
+                    let filter = doc! {"_id": doc_id.clone().unwrap()};
                     coll.find_one(filter, None).await?.unwrap()
                 };
                 let dt2 = doc.get("created_at").unwrap();
@@ -1830,9 +2630,12 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                         };
                         // Exclude files by default.
                         if file_data.path != file_data_default.path {
-                            let path = Path::new(&file_data.path);
-                            if path.is_file() {
-                                fs::remove_file(path)?;
+                            // A content-addressed file can be shared with other documents.
+                            if !field["is_content_addressed"].as_bool().unwrap()
+                                || Self::media_ref_discard(&coll, &file_data.path).await?
+                            {
+                                Self::delete_stored_media(field, &coll, &to_document(&file_data)?)
+                                    .await?;
                             }
                             //
                             *final_model_json
@@ -1854,9 +2657,12 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                         };
                         // Exclude files by default.
                         if img_data.path != img_data_default.path {
-                            let dir_path = Path::new(&img_data.path).parent().unwrap();
-                            if dir_path.is_dir() {
-                                fs::remove_dir_all(dir_path)?;
+                            // A content-addressed image can be shared with other documents.
+                            if !field["is_content_addressed"].as_bool().unwrap()
+                                || Self::media_ref_discard(&coll, &img_data.path).await?
+                            {
+                                Self::delete_stored_media(field, &coll, &to_document(&img_data)?)
+                                    .await?;
                             }
                             //
                             *final_model_json
@@ -1927,8 +2733,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
             let mut verified_data = self.check(client, Some((true, step == 2))).await?;
             let is_no_error: bool = verified_data.is_valid();
             let final_doc = verified_data.get_doc().unwrap();
-            let is_update: bool = !self.hash().is_empty();
-            let (collection_name, is_use_hash_slug, database_name) = {
+            let (collection_name, is_use_hash_slug, database_name, id_type) = {
                 // Get a key to access the metadata store.
                 let key = Self::key()?;
                 // Get metadata store.
@@ -1939,6 +2744,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                         meta.collection_name.clone(),
                         meta.is_use_hash_slug,
                         meta.database_name.clone(),
+                        meta.id_type.clone(),
                     )
                 } else {
                     Err(format!(
@@ -1951,6 +2757,8 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
             let coll = client
                 .database(database_name.as_str())
                 .collection::<Document>(collection_name.as_str());
+            // Determines the mode of accessing the database (create or update).
+            let is_update: bool = self.is_update(&id_type, &coll).await?;
             // Having fields with a controller of Slug type.
             if !is_update && is_no_error && is_use_hash_slug {
                 stop_step = 2;
@@ -1958,31 +2766,62 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
             // Save to database.
             // -------------------------------------------------------------------------------------
             if is_no_error {
+                // New stored files are deleted if the document is not written.
+                // Content-addressed files are referenced only after the document is written.
+                let media_fields = self.new_media_fields(&final_doc)?;
                 let hash_line;
                 if is_update {
                     // Update document.
                     hash_line = self.hash();
-                    let object_id = ObjectId::parse_str(hash_line.as_str())?;
-                    let query = doc! {"_id": object_id};
+                    let query = doc! {"_id": id_type.to_bson(hash_line.as_str())?};
                     let update = doc! {
                         "$set": final_doc.clone(),
                     };
+                    // Content-addressed files that will be replaced.
+                    let old_doc = if !media_fields.iter().any(|(_, field)| {
+                        field["is_content_addressed"].as_bool().unwrap_or_default()
+                    }) {
+                        None
+                    } else {
+                        coll.find_one(query.clone(), None).await?
+                    };
                     // Run hook.
                     self.pre_update(client).await;
                     // Update doc.
-                    coll.update_one(query, update, options_update.clone())
+                    // Hint: A unique index can be violated by a concurrent write.
+                    if let Err(err) = coll.update_one(query, update, options_update.clone()).await {
+                        Self::media_refs_discard(&coll, &final_doc, &media_fields).await?;
+                        verified_data.set_duplicate_key_errors(err)?;
+                        return Ok(verified_data);
+                    }
+                    Self::media_refs_commit(&coll, &final_doc, old_doc.as_ref(), &media_fields)
                         .await?;
                     // Run hook.
                     self.post_update(client).await;
                 } else {
                     // Run hook.
                     self.pre_create(client).await;
+                    // Add the document identifier.
+                    let mut new_doc = final_doc.clone();
+                    if let Some(doc_id) = id_type.new_id() {
+                        new_doc.insert("_id", doc_id);
+                    } else if !id_type.is_auto() {
+                        new_doc.insert("_id", id_type.to_bson(self.hash().as_str())?);
+                    }
                     // Create document.
-                    let result: InsertOneResult = coll
-                        .insert_one(final_doc.clone(), options_insert.clone())
-                        .await?;
+                    // Hint: A unique index can be violated by a concurrent write.
+                    let result: InsertOneResult =
+                        match coll.insert_one(new_doc, options_insert.clone()).await {
+                            Ok(result) => result,
+                            Err(err) => {
+                                Self::media_refs_discard(&coll, &final_doc, &media_fields).await?;
+                                verified_data.set_duplicate_key_errors(err)?;
+                                return Ok(verified_data);
+                            }
+                        };
+                    Self::media_refs_commit(&coll, &final_doc, None, &media_fields).await?;
                     // Get hash-line.
-                    hash_line = result.inserted_id.as_object_id().unwrap().to_hex();
+                    hash_line = IdType::to_hash(&result.inserted_id)?;
                     // Add hash-line to model instance.
                     self.set_hash(hash_line.clone());
                     // Run hook.
@@ -2029,7 +2868,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
     where
         Self: Serialize + DeserializeOwned + Sized,
     {
-        let (model_name, database_name, collection_name, fields_name, is_del_doc) = {
+        let (model_name, database_name, collection_name, fields_name, is_del_doc, id_type) = {
             // Get a key to access the metadata store.
             let key = Self::key()?;
             // Get metadata store.
@@ -2042,6 +2881,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     meta.collection_name.clone(),
                     meta.fields_name.clone(),
                     meta.is_del_doc,
+                    meta.id_type.clone(),
                 )
             } else {
                 Err(format!(
@@ -2065,7 +2905,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
             let coll = client
                 .database(database_name.as_str())
                 .collection::<Document>(collection_name.as_str());
-            // Get Model hash  for ObjectId.
+            // Get Model hash for the document identifier.
             let hash = self.hash();
             if hash.is_empty() {
                 Err(format!(
@@ -2073,9 +2913,8 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     An empty `hash` field is not allowed when deleting."
                 ))?
             }
-            let object_id = ObjectId::parse_str(hash.as_str())?;
             // Create query.
-            let query = doc! {"_id": object_id};
+            let query = doc! {"_id": id_type.to_bson(hash.as_str())?};
             // Removeve files
             if let Some(document) = coll.find_one(query.clone(), None).await? {
                 let model_json = self.self_to_json_val()?;
@@ -2096,12 +2935,13 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                                 } else {
                                     FileData::default()
                                 };
-                                // Exclude files by default.
-                                if path != file_data_default.path {
-                                    let path = Path::new(path);
-                                    if path.is_file() {
-                                        fs::remove_file(path)?;
-                                    }
+                                // Exclude files by default and shared files.
+                                if path != file_data_default.path
+                                    && (!field["is_content_addressed"].as_bool().unwrap()
+                                        || Self::media_ref_release(&coll, path).await?)
+                                {
+                                    let storage = Self::field_storage(field, &coll).await?;
+                                    storage.delete(path).await?;
                                 }
                             } else {
                                 Err(format!(
@@ -2112,7 +2952,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                         } else if field_type == "ImageField" {
                             if let Some(info_file) = document.get(field_name).unwrap().as_document()
                             {
-                                let path = info_file.get_str("path")?;
+                                let image_data = from_document::<ImageData>(info_file.clone())?;
                                 let default = field.get("default").unwrap();
                                 //
                                 let img_data_default = if !default.is_null() {
@@ -2120,11 +2960,14 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                                 } else {
                                     ImageData::default()
                                 };
-                                // Exclude files by default.
-                                if path != img_data_default.path {
-                                    let dir_path = Path::new(path).parent().unwrap();
-                                    if dir_path.is_dir() {
-                                        fs::remove_dir_all(dir_path)?;
+                                // Exclude files by default and shared files.
+                                if image_data.path != img_data_default.path
+                                    && (!field["is_content_addressed"].as_bool().unwrap()
+                                        || Self::media_ref_release(&coll, &image_data.path).await?)
+                                {
+                                    let storage = Self::field_storage(field, &coll).await?;
+                                    for path in image_data.paths() {
+                                        storage.delete(path).await?;
                                     }
                                 }
                             } else {
@@ -2167,28 +3010,31 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
     /// ```
     /// let user = User::new().await?;
     /// let password = user.password.get().unwrap();
-    /// println!("{}", user.create_password_hash(&password)?);
+    /// println!("{}", User::create_password_hash(&password).await?);
     /// ```
     ///
-    fn create_password_hash(password: &str) -> Result<String, Box<dyn Error>> {
-        const CHARSET: &[u8] =
-            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789@#$%^&+=*!~)(";
-        const SALT_LEN: usize = 12;
-        let mut rng = rand::thread_rng();
-        let password: &[u8] = password.as_bytes();
-        let salt: String = (0..SALT_LEN)
-            .map(|_| {
-                let idx = rng.gen_range(0..CHARSET.len());
-                CHARSET[idx] as char
-            })
-            .collect();
-        let salt: &[u8] = salt.as_bytes();
-        let config = argon2::Config::default();
-        let hash: String = argon2::hash_encoded(password, salt, &config)?;
-        Ok(hash)
+    async fn create_password_hash(password: &str) -> Result<String, Box<dyn Error>> {
+        let argon2_params = {
+            // Get a key to access the metadata store.
+            let key = Self::key()?;
+            // Get metadata store.
+            let metadata = METADATA.lock().await;
+            // Get metadata of Model.
+            if let Some(meta) = metadata.get(&key) {
+                meta.argon2_params()
+            } else {
+                Err(format!(
+                    "Model key: `{key}` ; Method: `create_password_hash()` => \
+                    Failed to get data from cache.",
+                ))?
+            }
+        };
+        hash_password(password, &argon2_params)
     }
 
     /// Match the password from the user to the password in the database.
+    /// Hint: After a successful verification, a hash with outdated Argon2 parameters
+    /// ( or without the current pepper ) is replaced with a new one.
     // ---------------------------------------------------------------------------------------------
     ///
     /// # Example:
@@ -2208,7 +3054,7 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
     where
         Self: Serialize + DeserializeOwned + Sized,
     {
-        let (database_name, collection_name, model_name) = {
+        let (database_name, collection_name, model_name, id_type, argon2_params) = {
             // Get a key to access the metadata store.
             let key = Self::key()?;
             // Get metadata store.
@@ -2219,6 +3065,8 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                     meta.database_name.clone(),
                     meta.collection_name.clone(),
                     meta.model_name.clone(),
+                    meta.id_type.clone(),
+                    meta.argon2_params(),
                 )
             } else {
                 Err(format!(
@@ -2239,12 +3087,10 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                 An empty `hash` field is not allowed when updating."
             ))?
         }
-        // Convert hash-line to ObjectId.
-        let object_id = ObjectId::parse_str(hash.as_str())?;
         // Create a filter to search for a document.
-        let filter = doc! {"_id": object_id};
+        let filter = doc! {"_id": id_type.to_bson(hash.as_str())?};
         // An attempt to find the required document.
-        let doc = coll.find_one(filter, options).await?;
+        let doc = coll.find_one(filter.clone(), options).await?;
         // We check that for the given `hash` a document is found in the database.
         if doc.is_none() {
             Err(format!(
@@ -2271,7 +3117,19 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
             ""
         };
         // Password verification.
-        Ok(argon2::verify_encoded(password_hash, password.as_bytes())?)
+        let (is_valid, is_rehash) = verify_password_hash(password_hash, password, &argon2_params)?;
+        // Rehash the password with the current parameters.
+        // Hint: The filter includes the old hash, so as not to overwrite a concurrent change.
+        if is_valid && is_rehash {
+            let new_password_hash = hash_password(password, &argon2_params)?;
+            let mut query = filter;
+            query.insert("password", password_hash);
+            let update = doc! {
+                "$set": {"password": new_password_hash},
+            };
+            coll.update_one(query, update, None).await?;
+        }
+        Ok(is_valid)
     }
 
     /// For replace or recover password.
@@ -2311,14 +3169,19 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
         {
             err_msg = String::from("The old password does not match.");
         } else {
-            let (database_name, collection_name) = {
+            let (database_name, collection_name, id_type, argon2_params) = {
                 // Get a key to access the metadata store.
                 let key = Self::key()?;
                 // Get metadata store.
                 let metadata = METADATA.lock().await;
                 // Get metadata of Model.
                 if let Some(meta) = metadata.get(&key) {
-                    (meta.database_name.clone(), meta.collection_name.clone())
+                    (
+                        meta.database_name.clone(),
+                        meta.collection_name.clone(),
+                        meta.id_type.clone(),
+                        meta.argon2_params(),
+                    )
                 } else {
                     Err(format!(
                         "Model key: `{key}` ; Method: `verify_password()` => \
@@ -2332,11 +3195,9 @@ pub trait QPaladins: Main + Caching + Hooks + Validation + Addition {
                 .collection::<Document>(collection_name.as_str());
             // Get hash-line of Model.
             let hash = self.hash();
-            // Convert hash-line to ObjectId.
-            let object_id = ObjectId::parse_str(hash.as_str())?;
             // Create a filter to search for a document.
-            let query = doc! {"_id": object_id};
-            let new_password_hash = Self::create_password_hash(new_password)?;
+            let query = doc! {"_id": id_type.to_bson(hash.as_str())?};
+            let new_password_hash = hash_password(new_password, &argon2_params)?;
             let doc = doc! {"password": new_password_hash};
             let update = doc! {
                 "$set": doc,
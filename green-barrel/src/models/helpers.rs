@@ -1,8 +1,12 @@
 //! Collection of auxiliary Structures, Enumerations.
 
+use mongodb::bson::{oid::ObjectId, spec::BinarySubtype, Binary, Bson};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::{collections::HashMap, error::Error};
+use uuid::Uuid;
+
+use crate::models::password::Argon2Params;
 
 /// Metadata ( model parameters )
 // -------------------------------------------------------------------------------------------------
@@ -23,6 +27,12 @@ pub struct Meta {
     pub is_use_addition: bool,
     pub is_use_hooks: bool,
     pub is_use_hash_slug: bool,
+    // Type of document identifier `_id`.
+    pub id_type: IdType,
+    // Argon2 parameters for `PasswordField` ( empty string, 0 - use global parameters ).
+    pub argon2_variant: String,
+    pub argon2_mem_cost: u32,
+    pub argon2_time_cost: u32,
     // <field_name, field_value_type>
     pub field_value_type_map: HashMap<String, String>,
     // <field_name, fields_type>
@@ -31,6 +41,14 @@ pub struct Meta {
     pub default_value_map: HashMap<String, Value>,
     // List of field names that will not be saved to the database
     pub ignore_fields: Vec<String>,
+    // Renamed fields. Format: <new_field_name, old_field_name>
+    pub renamed_fields: HashMap<String, String>,
+    // Compound indexes. Format: [[(field_name, direction), ...], ...]
+    pub indexes: Vec<Vec<(String, i32)>>,
+    pub unique_indexes: Vec<Vec<(String, i32)>>,
+    // Options of the `$jsonSchema` collection validator.
+    pub validation_level: String,
+    pub validation_action: String,
     // Choice maps for fields type `choice`. Format: HashMap<field_name, choices>
     pub choice_str_map: HashMap<String, Vec<String>>,
     pub choice_i32_map: HashMap<String, Vec<i32>>,
@@ -57,10 +75,19 @@ impl Default for Meta {
             is_use_addition: false,
             is_use_hooks: false,
             is_use_hash_slug: false,
+            id_type: IdType::default(),
+            argon2_variant: String::new(),
+            argon2_mem_cost: 0_u32,
+            argon2_time_cost: 0_u32,
             field_value_type_map: HashMap::new(),
             field_type_map: HashMap::new(),
             default_value_map: HashMap::new(),
             ignore_fields: Vec::new(),
+            renamed_fields: HashMap::new(),
+            indexes: Vec::new(),
+            unique_indexes: Vec::new(),
+            validation_level: String::from("moderate"),
+            validation_action: String::from("error"),
             choice_str_map: HashMap::new(),
             choice_i32_map: HashMap::new(),
             choice_i64_map: HashMap::new(),
@@ -70,6 +97,84 @@ impl Default for Meta {
     }
 }
 
+impl Meta {
+    /// Get Argon2 parameters of Model ( taking into account global parameters ).
+    pub fn argon2_params(&self) -> Argon2Params {
+        Argon2Params::for_model(
+            &self.argon2_variant,
+            self.argon2_mem_cost,
+            self.argon2_time_cost,
+        )
+    }
+}
+
+/// Type of document identifier `_id` ( model parameter `id_type` ).
+// -------------------------------------------------------------------------------------------------
+#[derive(Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+pub enum IdType {
+    /// Generated by the database.
+    #[default]
+    ObjectId,
+    /// Generated automatically, UUID version 4 ( BSON binary subtype 4 ).
+    #[serde(rename = "UUID")]
+    Uuid,
+    /// Supplied by the caller through the `hash` field.
+    #[serde(rename = "String")]
+    Str,
+    /// Supplied by the caller through the `hash` field.
+    I64,
+}
+
+impl IdType {
+    /// Is the identifier generated automatically.
+    pub fn is_auto(&self) -> bool {
+        matches!(self, Self::ObjectId | Self::Uuid)
+    }
+    /// Get a new identifier for the document being created.
+    /// Hint: For ObjectId, the identifier is generated by the database.
+    pub fn new_id(&self) -> Option<Bson> {
+        match self {
+            Self::Uuid => Some(Bson::Binary(Binary {
+                subtype: BinarySubtype::Uuid,
+                bytes: Uuid::new_v4().as_bytes().to_vec(),
+            })),
+            _ => None,
+        }
+    }
+    /// Convert a hash-line to the document identifier `_id`.
+    pub fn to_bson(&self, hash: &str) -> Result<Bson, Box<dyn Error>> {
+        let id = match self {
+            Self::ObjectId => Bson::ObjectId(ObjectId::parse_str(hash)?),
+            Self::Uuid => Bson::Binary(Binary {
+                subtype: BinarySubtype::Uuid,
+                bytes: Uuid::parse_str(hash)?.as_bytes().to_vec(),
+            }),
+            Self::Str => Bson::String(hash.to_string()),
+            Self::I64 => Bson::Int64(hash.parse::<i64>()?),
+        };
+        Ok(id)
+    }
+    /// Convert the document identifier `_id` to a hash-line.
+    pub fn to_hash(id: &Bson) -> Result<String, Box<dyn Error>> {
+        let hash = match id {
+            Bson::ObjectId(object_id) => object_id.to_hex(),
+            Bson::Binary(binary) if binary.subtype == BinarySubtype::Uuid => {
+                Uuid::from_slice(binary.bytes.as_slice())?
+                    .hyphenated()
+                    .to_string()
+            }
+            Bson::String(val) => val.clone(),
+            Bson::Int64(val) => val.to_string(),
+            Bson::Int32(val) => val.to_string(),
+            _ => Err(format!(
+                "Unsupported type of document identifier `_id` - `{:?}`.",
+                id.element_type()
+            ))?,
+        };
+        Ok(hash)
+    }
+}
+
 /// Helper structures for inputFile fields type.
 // -------------------------------------------------------------------------------------------------
 #[derive(Serialize, Deserialize, Default, Clone, Debug)]
@@ -78,6 +183,8 @@ pub struct FileData {
     pub url: String,
     pub name: String,
     pub size: f64, // bytes
+    #[serde(default)]
+    pub checksum: String, // SHA-256 of the file contents, in hex format.
     pub is_delete: bool,
 }
 
@@ -99,9 +206,32 @@ pub struct ImageData {
     pub size: f64,   // bytes
     pub width: f64,  // pixels
     pub height: f64, // pixels
+    #[serde(default)]
+    pub checksum: String, // SHA-256 of the original image contents, in hex format.
+    #[serde(default)]
+    pub blurhash: String, // Placeholder for the frontend. Example: "LEHV6nWB2yk8pyo0adR*.7kCMdnj".
+    #[serde(default)]
+    pub dominant_color: String, // Placeholder for the frontend. Example: "#a1b2c3".
     pub is_delete: bool,
 }
 
+impl ImageData {
+    /// Paths of the original image and thumbnails ( non-empty only ).
+    pub fn paths(&self) -> Vec<&str> {
+        [
+            &self.path,
+            &self.path_xs,
+            &self.path_sm,
+            &self.path_md,
+            &self.path_lg,
+        ]
+        .iter()
+        .filter(|path| !path.is_empty())
+        .map(|path| path.as_str())
+        .collect()
+    }
+}
+
 /// To optimize the update_dyn_wig method.
 // -------------------------------------------------------------------------------------------------
 pub enum ControlArr<'a> {
@@ -0,0 +1,323 @@
+//! Image processing for ImageField ( orientation, thumbnails, output format ).
+//!
+//! Hint: Decoding, resizing and encoding are CPU-bound,
+//! therefore `process_image` performs them in a blocking thread pool ( `spawn_blocking` ).
+
+use exif::{In, Reader, Tag};
+use image::{
+    codecs::jpeg::JpegEncoder,
+    imageops::{self, FilterType},
+    io::Reader as ImageReader,
+    DynamicImage, GenericImageView, ImageFormat, Rgba, RgbaImage,
+};
+use serde::Deserialize;
+use serde_json::value::Value;
+use std::{error::Error, io::Cursor, sync::Arc};
+use tokio::task::spawn_blocking;
+
+/// Image processing options of ImageField.
+// *************************************************************************************************
+#[derive(Deserialize, Clone, Debug)]
+pub struct ImageOptions {
+    pub is_quality: bool,
+    pub is_auto_orient: bool,
+    pub is_strip_metadata: bool,
+    pub output_format: String,
+    pub jpeg_quality: u8,
+    pub resize_mode: String,
+    pub aspect_ratio: (u32, u32),
+    pub pad_color: [u8; 4],
+    pub is_placeholder: bool,
+}
+
+impl ImageOptions {
+    /// Get options from the ImageField parameters.
+    pub fn from_field(field: &Value) -> Result<Self, Box<dyn Error>> {
+        let options = serde_json::from_value::<Self>(field.clone())?;
+        if !["", "jpeg", "png", "webp"].contains(&options.output_format.as_str()) {
+            Err(format!(
+                "Parameter: `output_format` => \
+                Valid values - \"\", \"jpeg\", \"png\", \"webp\". Received: \"{}\".",
+                options.output_format
+            ))?
+        }
+        if !(1..=100).contains(&options.jpeg_quality) {
+            Err(format!(
+                "Parameter: `jpeg_quality` => \
+                The value must be in the range from 1 to 100. Received: {}.",
+                options.jpeg_quality
+            ))?
+        }
+        if !["scale", "fill", "pad"].contains(&options.resize_mode.as_str()) {
+            Err(format!(
+                "Parameter: `resize_mode` => \
+                Valid values - \"scale\", \"fill\", \"pad\". Received: \"{}\".",
+                options.resize_mode
+            ))?
+        }
+        if options.aspect_ratio.0 == 0 || options.aspect_ratio.1 == 0 {
+            Err("Parameter: `aspect_ratio` => Zero values are not allowed.")?
+        }
+        Ok(options)
+    }
+
+    /// Filter for resizing images.
+    pub fn filter_type(&self) -> FilterType {
+        if self.is_quality {
+            FilterType::Triangle
+        } else {
+            FilterType::Nearest
+        }
+    }
+
+    /// Format of output images.
+    pub fn output_format(&self, source_format: ImageFormat) -> ImageFormat {
+        match self.output_format.as_str() {
+            "jpeg" => ImageFormat::Jpeg,
+            "png" => ImageFormat::Png,
+            "webp" => ImageFormat::WebP,
+            _ => source_format,
+        }
+    }
+
+    /// Whether the original image should be re-encoded.
+    pub fn is_reencode(&self, orientation: u32) -> bool {
+        self.is_strip_metadata
+            || !self.output_format.is_empty()
+            || (self.is_auto_orient && orientation > 1)
+    }
+}
+
+/// Get the EXIF orientation of the image ( from 1 to 8 ).
+/// Hint: 1 - normal orientation, also returned if there is no EXIF data.
+// *************************************************************************************************
+pub fn exif_orientation(data: &[u8]) -> u32 {
+    Reader::new()
+        .read_from_container(&mut Cursor::new(data))
+        .ok()
+        .and_then(|exif| {
+            exif.get_field(Tag::Orientation, In::PRIMARY)
+                .and_then(|field| field.value.get_uint(0))
+        })
+        .filter(|orientation| (1..=8).contains(orientation))
+        .unwrap_or(1)
+}
+
+/// Rotate and flip the image according to the EXIF orientation.
+// *************************************************************************************************
+pub fn apply_orientation(img: DynamicImage, orientation: u32) -> DynamicImage {
+    match orientation {
+        2 => img.fliph(),
+        3 => img.rotate180(),
+        4 => img.flipv(),
+        5 => img.rotate90().fliph(),
+        6 => img.rotate90(),
+        7 => img.rotate270().fliph(),
+        8 => img.rotate270(),
+        _ => img,
+    }
+}
+
+/// Calculate the maximum size for a thumbnail.
+/// Hint: (0.0, 0.0) - the image is smaller than the thumbnail.
+// *************************************************************************************************
+pub fn calculate_thumbnail_size(width: f64, height: f64, max_size: f64) -> (f64, f64) {
+    if width > height {
+        if width > max_size {
+            return (max_size, (height * (max_size / width)).floor());
+        }
+    } else if height > max_size {
+        return ((width * (max_size / height)).floor(), max_size);
+    }
+    (0.0, 0.0)
+}
+
+/// Create a thumbnail of the image.
+/// Hint: None - the image is smaller than the thumbnail.
+// *************************************************************************************************
+pub fn make_thumbnail(
+    img: &DynamicImage,
+    max_size: u32,
+    options: &ImageOptions,
+) -> Option<DynamicImage> {
+    let (width, height) = img.dimensions();
+    if width.max(height) <= max_size {
+        return None;
+    }
+    let filter_type = options.filter_type();
+    // Size of the thumbnail with the specified aspect ratio.
+    let (ratio_width, ratio_height) = options.aspect_ratio;
+    let (box_width, box_height) = if ratio_width >= ratio_height {
+        let box_height = (max_size as f64 * ratio_height as f64 / ratio_width as f64).round();
+        (max_size, (box_height as u32).max(1))
+    } else {
+        let box_width = (max_size as f64 * ratio_width as f64 / ratio_height as f64).round();
+        ((box_width as u32).max(1), max_size)
+    };
+    match options.resize_mode.as_str() {
+        "fill" => Some(img.resize_to_fill(box_width, box_height, filter_type)),
+        "pad" => {
+            let thumbnail = img.resize(box_width, box_height, filter_type);
+            let mut canvas = RgbaImage::from_pixel(box_width, box_height, Rgba(options.pad_color));
+            let x = (box_width - thumbnail.width()) / 2;
+            let y = (box_height - thumbnail.height()) / 2;
+            imageops::overlay(&mut canvas, &thumbnail.to_rgba8(), x as i64, y as i64);
+            Some(DynamicImage::ImageRgba8(canvas))
+        }
+        _ => {
+            let (width, height) =
+                calculate_thumbnail_size(width as f64, height as f64, max_size as f64);
+            Some(img.resize_exact(width as u32, height as u32, filter_type))
+        }
+    }
+}
+
+/// Encode the image to the specified format.
+/// Hint: Metadata is not saved.
+// *************************************************************************************************
+pub fn encode_image(
+    img: &DynamicImage,
+    format: ImageFormat,
+    jpeg_quality: u8,
+) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let mut data = Cursor::new(Vec::<u8>::new());
+    match format {
+        ImageFormat::Jpeg => {
+            let encoder = JpegEncoder::new_with_quality(&mut data, jpeg_quality);
+            DynamicImage::ImageRgb8(img.to_rgb8()).write_with_encoder(encoder)?;
+        }
+        ImageFormat::WebP => {
+            DynamicImage::ImageRgba8(img.to_rgba8()).write_to(&mut data, format)?;
+        }
+        _ => img.write_to(&mut data, format)?,
+    }
+    Ok(data.into_inner())
+}
+
+/// Compute placeholders for the frontend - ( blurhash, dominant color ).
+/// Hint: The dominant color is the average color of the image, in hex format.
+// *************************************************************************************************
+pub fn placeholders(img: &DynamicImage) -> Result<(String, String), Box<dyn Error + Send + Sync>> {
+    let small_img = img.thumbnail(32, 32).to_rgba8();
+    let blurhash = blurhash::encode(
+        4,
+        3,
+        small_img.width(),
+        small_img.height(),
+        small_img.as_raw(),
+    )?;
+    let pixel = imageops::resize(&small_img, 1, 1, FilterType::Triangle)
+        .get_pixel(0, 0)
+        .0;
+    let dominant_color = format!("#{:02x}{:02x}{:02x}", pixel[0], pixel[1], pixel[2]);
+    Ok((blurhash, dominant_color))
+}
+
+/// Get the image width and height from the header, without decoding
+/// ( taking into account the EXIF orientation ).
+// *************************************************************************************************
+pub fn image_dimensions(data: &[u8], orientation: u32) -> Result<(u32, u32), Box<dyn Error>> {
+    let (width, height) = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()?
+        .into_dimensions()?;
+    if orientation > 4 {
+        Ok((height, width))
+    } else {
+        Ok((width, height))
+    }
+}
+
+/// Result of image processing.
+// *************************************************************************************************
+#[derive(Debug)]
+pub struct ProcessedImage {
+    /// Original image ( re-encoded if necessary ).
+    pub data: Vec<u8>,
+    /// Thumbnails - ( size name, encoded image ).
+    /// Hint: Sizes larger than the original image are skipped.
+    pub thumbnails: Vec<(String, Vec<u8>)>,
+    /// Placeholder - blurhash string.
+    pub blurhash: String,
+    /// Placeholder - dominant color in hex format.
+    pub dominant_color: String,
+}
+
+/// Decode the image, re-encode the original and create thumbnails.
+/// Hint: Work is performed in a blocking thread pool ( `spawn_blocking` ),
+/// thumbnails are created in parallel.
+// *************************************************************************************************
+pub async fn process_image(
+    data: Vec<u8>,
+    orientation: u32,
+    output_format: ImageFormat,
+    options: &ImageOptions,
+    thumbnails: &[(String, u32)],
+) -> Result<ProcessedImage, Box<dyn Error>> {
+    let is_reencode = options.is_reencode(orientation);
+    if !is_reencode && thumbnails.is_empty() && !options.is_placeholder {
+        return Ok(ProcessedImage {
+            data,
+            thumbnails: Vec::new(),
+            blurhash: String::new(),
+            dominant_color: String::new(),
+        });
+    }
+    let jpeg_quality = options.jpeg_quality;
+    // Decode the image.
+    let (img, data) = spawn_blocking(move || -> Result<_, Box<dyn Error + Send + Sync>> {
+        let img = apply_orientation(image::load_from_memory(&data)?, orientation);
+        Ok((Arc::new(img), data))
+    })
+    .await?
+    .map_err(|err| -> Box<dyn Error> { err })?;
+    // Create thumbnails.
+    let handles = thumbnails
+        .iter()
+        .map(|(size_name, max_size)| {
+            let img = img.clone();
+            let options = options.clone();
+            let size_name = size_name.clone();
+            let max_size = *max_size;
+            spawn_blocking(move || -> Result<_, Box<dyn Error + Send + Sync>> {
+                if let Some(thumbnail) = make_thumbnail(&img, max_size, &options) {
+                    let thumb_data = encode_image(&thumbnail, output_format, jpeg_quality)?;
+                    Ok(Some((size_name, thumb_data)))
+                } else {
+                    Ok(None)
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    // Compute placeholders.
+    let placeholders = if options.is_placeholder {
+        let img = img.clone();
+        Some(spawn_blocking(move || placeholders(&img)))
+    } else {
+        None
+    };
+    // Re-encode the original image.
+    let data = if is_reencode {
+        spawn_blocking(move || encode_image(&img, output_format, jpeg_quality))
+            .await?
+            .map_err(|err| -> Box<dyn Error> { err })?
+    } else {
+        data
+    };
+    let (blurhash, dominant_color) = if let Some(handle) = placeholders {
+        handle.await?.map_err(|err| -> Box<dyn Error> { err })?
+    } else {
+        (String::new(), String::new())
+    };
+    let mut thumbnails = Vec::new();
+    for handle in handles {
+        if let Some(thumbnail) = handle.await?.map_err(|err| -> Box<dyn Error> { err })? {
+            thumbnails.push(thumbnail);
+        }
+    }
+    Ok(ProcessedImage {
+        data,
+        thumbnails,
+        blurhash,
+        dominant_color,
+    })
+}
@@ -5,7 +5,7 @@ use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
 use std::error::Error;
 
-use crate::models::converters::Converters;
+use crate::{migration::indexes::duplicate_key_fields, models::converters::Converters};
 
 /// Output data for delete(), update_password(), delete_many(), delete_one, drop() methods.
 // =================================================================================================
@@ -215,6 +215,13 @@ impl OutputData2 {
     /// ```
     ///
     pub fn obj_id(&self) -> Result<Option<ObjectId>, Box<dyn Error>> {
+        // Only for `id_type = "ObjectId"` the hash-line can be converted to ObjectId.
+        let id_type = self.final_model_json["hash"]["id_type"]
+            .as_str()
+            .unwrap_or("ObjectId");
+        if id_type != "ObjectId" {
+            return Ok(None);
+        }
         let hash = self.hash();
         if let Ok(obj_id) = ObjectId::parse_str(hash.as_str()) {
             return Ok(Some(obj_id));
@@ -444,4 +451,30 @@ impl OutputData2 {
     pub fn model_json(&self) -> Value {
         self.final_model_json.clone()
     }
+
+    /// Add the `not_unique` error to the fields whose values violate a unique index
+    /// ( for example, after a concurrent write ).
+    // ---------------------------------------------------------------------------------------------
+    /// Hint: Returns the original error if it is not a duplicate key error of the model fields.
+    ///
+    pub(crate) fn set_duplicate_key_errors(
+        &mut self,
+        err: mongodb::error::Error,
+    ) -> Result<(), Box<dyn Error>> {
+        let field_names = duplicate_key_fields(&err, &self.fields_name)?;
+        if field_names.is_empty() {
+            Err(err)?
+        }
+        let err_msg = json!(t!("not_unique"));
+        for field_name in field_names.iter() {
+            let errors = self.final_model_json[field_name]["errors"]
+                .as_array_mut()
+                .unwrap();
+            if !errors.contains(&err_msg) {
+                errors.push(err_msg.clone());
+            }
+        }
+        self.is_valid = false;
+        Ok(())
+    }
 }
@@ -0,0 +1,298 @@
+//! Password hashing ( Argon2 ) and password strength policy.
+//!
+//! Hint: The global Argon2 parameters are set via `set_argon2_params`,
+//! the model parameters `argon2_variant`, `argon2_mem_cost` and `argon2_time_cost`
+//! take precedence over them.
+//! Hashes with outdated parameters are rehashed after a successful verification.
+
+use rand::Rng;
+use std::{error::Error, fmt};
+
+use crate::store::ARGON2_PARAMS;
+
+/// Argon2 hashing parameters.
+/// Hint: The default values match the OWASP recommendations ( Argon2id, m=19456, t=2, p=1 ).
+// *************************************************************************************************
+#[derive(Clone, PartialEq, Eq)]
+pub struct Argon2Params {
+    /// Valid values: "argon2d", "argon2i", "argon2id".
+    pub variant: String,
+    /// Memory cost in kibibytes.
+    pub mem_cost: u32,
+    /// Number of iterations.
+    pub time_cost: u32,
+    /// Degree of parallelism.
+    pub lanes: u32,
+    /// Length of the hash in bytes.
+    pub hash_length: u32,
+    /// Server-side secret ( pepper ), which is not stored in the database.
+    /// Hint: Empty string - the pepper is not used.
+    pub pepper: String,
+}
+
+impl fmt::Debug for Argon2Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Argon2Params")
+            .field("variant", &self.variant)
+            .field("mem_cost", &self.mem_cost)
+            .field("time_cost", &self.time_cost)
+            .field("lanes", &self.lanes)
+            .field("hash_length", &self.hash_length)
+            .field("pepper", &"***")
+            .finish()
+    }
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        Self {
+            variant: String::from("argon2id"),
+            mem_cost: 19456,
+            time_cost: 2,
+            lanes: 1,
+            hash_length: 32,
+            pepper: String::new(),
+        }
+    }
+}
+
+impl Argon2Params {
+    /// Get the global parameters.
+    pub fn global() -> Self {
+        ARGON2_PARAMS.read().unwrap().clone()
+    }
+
+    /// Get parameters of Model - model parameters override the global ones.
+    /// Hint: An empty variant and zero values mean - use the global parameter.
+    pub fn for_model(variant: &str, mem_cost: u32, time_cost: u32) -> Self {
+        let mut params = Self::global();
+        if !variant.is_empty() {
+            params.variant = variant.to_string();
+        }
+        if mem_cost > 0 {
+            params.mem_cost = mem_cost;
+        }
+        if time_cost > 0 {
+            params.time_cost = time_cost;
+        }
+        params
+    }
+
+    /// Check the parameters for correctness.
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        argon2::Variant::from_str(&self.variant).map_err(|_| {
+            format!(
+                "Argon2 => Invalid variant `{}`. Valid values: argon2d | argon2i | argon2id",
+                self.variant
+            )
+        })?;
+        if self.lanes == 0 || self.time_cost == 0 {
+            Err("Argon2 => The `lanes` and `time_cost` parameters must be greater than zero.")?
+        }
+        if self.mem_cost < 8 * self.lanes {
+            Err(format!(
+                "Argon2 => The `mem_cost` parameter must be at least {} ( 8 * lanes ).",
+                8 * self.lanes
+            ))?
+        }
+        if self.hash_length < 4 {
+            Err("Argon2 => The `hash_length` parameter must be at least 4.")?
+        }
+        Ok(())
+    }
+
+    /// Get the configuration for the `argon2` crate.
+    pub fn config(&self) -> Result<argon2::Config<'_>, Box<dyn Error>> {
+        self.validate()?;
+        Ok(argon2::Config {
+            variant: argon2::Variant::from_str(&self.variant)?,
+            mem_cost: self.mem_cost,
+            time_cost: self.time_cost,
+            lanes: self.lanes,
+            hash_length: self.hash_length,
+            secret: self.pepper.as_bytes(),
+            ..argon2::Config::default()
+        })
+    }
+
+    /// Whether the hash was created with parameters other than the current ones.
+    /// Hint: Hashes in an unknown format are also considered outdated.
+    pub fn is_outdated(&self, hash: &str) -> bool {
+        // Format: $argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>
+        let parts = hash.split('$').collect::<Vec<&str>>();
+        if parts.len() != 6 || !parts[0].is_empty() {
+            return true;
+        }
+        let hash_length = parts[5].trim_end_matches('=').len() * 3 / 4;
+        parts[1] != self.variant
+            || parts[2] != format!("v={}", argon2::Version::Version13.as_u32())
+            || parts[3] != format!("m={},t={},p={}", self.mem_cost, self.time_cost, self.lanes)
+            || hash_length != self.hash_length as usize
+    }
+}
+
+/// Set the global Argon2 parameters.
+///
+/// # Example:
+///
+/// ```
+/// set_argon2_params(Argon2Params {
+///     mem_cost: 65536,
+///     time_cost: 3,
+///     pepper: std::env::var("PASSWORD_PEPPER")?,
+///     ..Default::default()
+/// })?;
+/// ```
+// *************************************************************************************************
+pub fn set_argon2_params(params: Argon2Params) -> Result<(), Box<dyn Error>> {
+    params.validate()?;
+    *ARGON2_PARAMS.write().unwrap() = params;
+    Ok(())
+}
+
+/// Generate password hash ( PHC string format ).
+// *************************************************************************************************
+pub fn hash_password(password: &str, params: &Argon2Params) -> Result<String, Box<dyn Error>> {
+    const CHARSET: &[u8] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789@#$%^&+=*!~)(";
+    const SALT_LEN: usize = 12;
+    let mut rng = rand::thread_rng();
+    let salt: String = (0..SALT_LEN)
+        .map(|_| {
+            let idx = rng.gen_range(0..CHARSET.len());
+            CHARSET[idx] as char
+        })
+        .collect();
+    let config = params.config()?;
+    Ok(argon2::hash_encoded(
+        password.as_bytes(),
+        salt.as_bytes(),
+        &config,
+    )?)
+}
+
+/// Verify the password against the hash - ( is valid, is rehash needed ).
+/// Hint: If the pepper is set, hashes created before it was introduced are also accepted
+/// and marked for rehashing.
+// *************************************************************************************************
+pub fn verify_password_hash(
+    hash: &str,
+    password: &str,
+    params: &Argon2Params,
+) -> Result<(bool, bool), Box<dyn Error>> {
+    if hash.is_empty() {
+        return Ok((false, false));
+    }
+    let password = password.as_bytes();
+    let pepper = params.pepper.as_bytes();
+    if argon2::verify_encoded_ext(hash, password, pepper, &[])? {
+        return Ok((true, params.is_outdated(hash)));
+    }
+    if !pepper.is_empty() && argon2::verify_encoded(hash, password)? {
+        return Ok((true, true));
+    }
+    Ok((false, false))
+}
+
+/// Number of character classes in the password - lowercase, uppercase, digits, other.
+// *************************************************************************************************
+pub fn char_classes_count(password: &str) -> usize {
+    [
+        password.chars().any(|chr| chr.is_lowercase()),
+        password.chars().any(|chr| chr.is_uppercase()),
+        password.chars().any(|chr| chr.is_ascii_digit()),
+        password
+            .chars()
+            .any(|chr| !chr.is_alphanumeric() && !chr.is_whitespace()),
+    ]
+    .iter()
+    .filter(|flag| **flag)
+    .count()
+}
+
+/// Check the password against the list of common passwords ( case insensitive ).
+// *************************************************************************************************
+pub fn is_common_password(password: &str) -> bool {
+    let password = password.to_lowercase();
+    COMMON_PASSWORDS.contains(&password.as_str())
+}
+
+/// List of the most common passwords.
+// *************************************************************************************************
+pub const COMMON_PASSWORDS: &[&str] = &[
+    "000000",
+    "00000000",
+    "111111",
+    "11111111",
+    "112233",
+    "121212",
+    "123123",
+    "123321",
+    "1234",
+    "12345",
+    "123456",
+    "1234567",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "123qwe",
+    "1q2w3e",
+    "1q2w3e4r",
+    "1q2w3e4r5t",
+    "1qaz2wsx",
+    "654321",
+    "666666",
+    "696969",
+    "7777777",
+    "87654321",
+    "888888",
+    "987654321",
+    "a123456",
+    "aa123456",
+    "abc123",
+    "abcd1234",
+    "access",
+    "admin",
+    "admin123",
+    "administrator",
+    "asdfgh",
+    "asdfghjkl",
+    "azerty",
+    "baseball",
+    "batman",
+    "charlie",
+    "computer",
+    "dragon",
+    "football",
+    "freedom",
+    "hello",
+    "hello123",
+    "iloveyou",
+    "login",
+    "letmein",
+    "master",
+    "michael",
+    "monkey",
+    "mustang",
+    "passw0rd",
+    "password",
+    "password1",
+    "password12",
+    "password123",
+    "princess",
+    "qazwsx",
+    "qwe123",
+    "qwerty",
+    "qwerty123",
+    "qwertyuiop",
+    "shadow",
+    "starwars",
+    "sunshine",
+    "superman",
+    "trustno1",
+    "welcome",
+    "welcome1",
+    "whatever",
+    "zaq12wsx",
+    "zxcvbnm",
+];
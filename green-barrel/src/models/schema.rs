@@ -0,0 +1,329 @@
+//! Export of the Model as JSON Schema and OpenAPI 3 component objects.
+//!
+//! The schemas describe the values of the fields in the form they are exchanged with the frontend
+//! ( web forms, API ) - dates are strings, `hash` is the hex string of ObjectId.
+//! `hash`, `created_at`, `updated_at` and fields with `readonly` or `disabled` are `readOnly`,
+//! passwords and fields from `ignore_fields` are `writeOnly`.
+//! Hint: Component objects use the OpenAPI 3.0 dialect ( `nullable` ),
+//! for OpenAPI 3.1 use the JSON Schema of the Model.
+//!
+//! # Example:
+//!
+//! ```
+//! let json_schema = User::json_schema().await?;
+//! let components = {
+//!     let metadata = METADATA.lock().await;
+//!     openapi_components(metadata.values())?
+//! };
+//! std::fs::write("components.json", serde_json::to_string_pretty(&components)?)?;
+//! ```
+
+use serde_json::{json, Map, Value};
+use std::error::Error;
+
+use crate::models::helpers::Meta;
+
+/// JSON Schema dialect of the exported schemas.
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Dialect {
+    JsonSchema,
+    OpenApi,
+}
+
+/// Get the JSON Schema of the Model.
+// *************************************************************************************************
+pub fn model_json_schema(meta: &Meta) -> Result<Value, Box<dyn Error>> {
+    let mut schema = Map::new();
+    schema.insert("$schema".into(), json!(JSON_SCHEMA_DIALECT));
+    schema.extend(object_schema(meta, Dialect::JsonSchema)?);
+    Ok(Value::Object(schema))
+}
+
+/// Get the OpenAPI 3 schema object of the Model.
+// *************************************************************************************************
+pub fn model_openapi_schema(meta: &Meta) -> Result<Value, Box<dyn Error>> {
+    Ok(Value::Object(object_schema(meta, Dialect::OpenApi)?))
+}
+
+/// Get the OpenAPI 3 `components` object with the schemas of Models.
+/// Hint: Schemas are named after the Models.
+// *************************************************************************************************
+pub fn openapi_components<'a, I>(metas: I) -> Result<Value, Box<dyn Error>>
+where
+    I: IntoIterator<Item = &'a Meta>,
+{
+    let mut schemas = Map::new();
+    for meta in metas {
+        if schemas.contains_key(&meta.model_name) {
+            Err(format!(
+                "Model: `{}` ; Method: `openapi_components()` => \
+                 Models with the same name.",
+                meta.model_name
+            ))?
+        }
+        schemas.insert(meta.model_name.clone(), model_openapi_schema(meta)?);
+    }
+    Ok(json!({ "schemas": schemas }))
+}
+
+/// Get the schema of the Model as an object.
+fn object_schema(meta: &Meta, dialect: Dialect) -> Result<Map<String, Value>, Box<dyn Error>> {
+    let mut required = Vec::<String>::new();
+    let mut properties = Map::new();
+    for field_name in meta.fields_name.iter() {
+        let field = &meta.model_json[field_name];
+        let field_type = meta
+            .field_type_map
+            .get(field_name)
+            .map_or("", String::as_str);
+        let is_required = field["required"].as_bool().unwrap_or_default();
+        if is_required {
+            required.push(field_name.clone());
+        }
+        let mut schema = field_schema(field_name, field, meta, dialect, is_required)?;
+        if let Some(label) = field["label"].as_str().filter(|val| !val.is_empty()) {
+            schema.insert("title".into(), json!(label));
+        }
+        if let Some(hint) = field["hint"].as_str().filter(|val| !val.is_empty()) {
+            schema.insert("description".into(), json!(hint));
+        }
+        if !field["default"].is_null() {
+            schema.insert("default".into(), field["default"].clone());
+        }
+        // Fields that are not saved are only accepted from the user.
+        if meta.ignore_fields.contains(field_name) || field_type == "PasswordField" {
+            schema.insert("writeOnly".into(), json!(true));
+        } else if ["hash", "created_at", "updated_at"].contains(&field_name.as_str())
+            || field["readonly"].as_bool().unwrap_or_default()
+            || field["disabled"].as_bool().unwrap_or_default()
+        {
+            schema.insert("readOnly".into(), json!(true));
+        }
+        properties.insert(field_name.clone(), Value::Object(schema));
+    }
+    let mut schema = Map::new();
+    schema.insert("title".into(), json!(meta.model_name));
+    schema.insert("type".into(), json!("object"));
+    schema.insert("properties".into(), Value::Object(properties));
+    if !required.is_empty() {
+        schema.insert("required".into(), json!(required));
+    }
+    Ok(schema)
+}
+
+/// Get the schema of the field value.
+fn field_schema(
+    field_name: &str,
+    field: &Value,
+    meta: &Meta,
+    dialect: Dialect,
+    is_required: bool,
+) -> Result<Map<String, Value>, Box<dyn Error>> {
+    let field_type = meta
+        .field_type_map
+        .get(field_name)
+        .map_or("", String::as_str);
+    let value_type = meta
+        .field_value_type_map
+        .get(field_name)
+        .map_or("", String::as_str);
+    let mut schema = Map::new();
+    match field_type {
+        // The value can be of any type, unless the field has its own schema.
+        "JsonField" => {
+            if let Some(json_schema) = field["json_schema"].as_str().filter(|val| !val.is_empty()) {
+                let json_schema = serde_json::from_str::<Value>(json_schema).map_err(|err| {
+                    format!(
+                        "Model: `{}` > Field: `{field_name}` ; Method: `json_schema()` => \
+                         Invalid JSON Schema - {err}",
+                        meta.model_name
+                    )
+                })?;
+                if let Value::Object(mut json_schema) = json_schema {
+                    json_schema.remove("$schema");
+                    schema = json_schema;
+                }
+            }
+            return Ok(schema);
+        }
+        "FileField" | "ImageField" => {
+            set_type(&mut schema, "object", dialect, is_required);
+            schema.insert("properties".into(), file_properties(field_type));
+            return Ok(schema);
+        }
+        _ => {}
+    }
+    // Values of choice fields must match the choices.
+    let choices = if field_type.starts_with("Choice") && !field_type.contains("Dyn") {
+        choices(field_name, meta).filter(|choices| !choices.is_empty())
+    } else {
+        None
+    };
+    if let Some(item_type) = value_type
+        .strip_prefix("Vec<")
+        .and_then(|val| val.strip_suffix('>'))
+    {
+        set_type(&mut schema, "array", dialect, is_required);
+        let mut items = Map::new();
+        let json_type = insert_value_format(&mut items, item_type, field);
+        items.insert("type".into(), json!(json_type));
+        if let Some(choices) = choices {
+            items.insert("enum".into(), Value::Array(choices));
+        } else {
+            insert_limits(&mut items, field, field_type);
+        }
+        schema.insert("items".into(), Value::Object(items));
+        if let Some(min_items) = field["min_items"].as_u64().filter(|val| *val > 0) {
+            schema.insert("minItems".into(), json!(min_items));
+        }
+        if let Some(max_items) = field["max_items"].as_u64().filter(|val| *val > 0) {
+            schema.insert("maxItems".into(), json!(max_items));
+        }
+        return Ok(schema);
+    }
+    let json_type = insert_value_format(&mut schema, value_type, field);
+    set_type(&mut schema, json_type, dialect, is_required);
+    if let Some(format) = string_format(field_type, field) {
+        schema.insert("format".into(), json!(format));
+    }
+    if let Some(mut choices) = choices {
+        // Hint: In OpenAPI 3.0, `nullable` does not extend the list of choices.
+        if !is_required {
+            choices.push(Value::Null);
+        }
+        schema.insert("enum".into(), Value::Array(choices));
+    } else {
+        insert_limits(&mut schema, field, field_type);
+    }
+    Ok(schema)
+}
+
+/// Set the type of the value.
+/// Hint: Not required fields allow `null` ( empty values ).
+fn set_type(schema: &mut Map<String, Value>, json_type: &str, dialect: Dialect, is_required: bool) {
+    if is_required {
+        schema.insert("type".into(), json!(json_type));
+    } else if dialect == Dialect::OpenApi {
+        schema.insert("type".into(), json!(json_type));
+        schema.insert("nullable".into(), json!(true));
+    } else {
+        schema.insert("type".into(), json!([json_type, "null"]));
+    }
+}
+
+/// Insert the numeric format of the value and get its type.
+fn insert_value_format(
+    schema: &mut Map<String, Value>,
+    value_type: &str,
+    field: &Value,
+) -> &'static str {
+    let (json_type, format) = match value_type {
+        "i32" => ("integer", Some("int32")),
+        "u32" | "i64" => ("integer", Some("int64")),
+        "f64" => ("number", Some("double")),
+        "bool" => ("boolean", None),
+        _ => ("string", None),
+    };
+    if let Some(format) = format {
+        schema.insert("format".into(), json!(format));
+    }
+    if value_type == "u32" && !field["min"].is_number() {
+        schema.insert("minimum".into(), json!(0));
+    }
+    json_type
+}
+
+/// Get the format of the string value.
+fn string_format(field_type: &str, field: &Value) -> Option<&'static str> {
+    match field_type {
+        "DateField" => Some("date"),
+        "DateTimeField" | "HiddenDateTimeField" => Some("date-time"),
+        "TimeField" => Some("time"),
+        "EmailField" => Some("email"),
+        "URLField" => Some("uri"),
+        "UUIDField" => Some("uuid"),
+        "PasswordField" => Some("password"),
+        // Hint: The type of the IP address is specified in the `field_type` attribute.
+        "IPField" => match field["field_type"].as_str() {
+            Some("IPv4Field") => Some("ipv4"),
+            Some("IPv6Field") => Some("ipv6"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Get the choices of the field from the choice maps.
+fn choices(field_name: &str, meta: &Meta) -> Option<Vec<Value>> {
+    if let Some(choices) = meta.choice_str_map.get(field_name) {
+        Some(choices.iter().map(|val| json!(val)).collect())
+    } else if let Some(choices) = meta.choice_i32_map.get(field_name) {
+        Some(choices.iter().map(|val| json!(val)).collect())
+    } else if let Some(choices) = meta.choice_i64_map.get(field_name) {
+        Some(choices.iter().map(|val| json!(val)).collect())
+    } else {
+        meta.choice_f64_map
+            .get(field_name)
+            .map(|choices| choices.iter().map(|val| json!(val)).collect())
+    }
+}
+
+/// Insert the limits of the value - minLength, maxLength, pattern, minimum, maximum.
+fn insert_limits(schema: &mut Map<String, Value>, field: &Value, field_type: &str) {
+    // Hashes are generated.
+    if field_type == "HashField" {
+        return;
+    }
+    if let Some(minlength) = field["minlength"].as_u64().filter(|val| *val > 0) {
+        schema.insert("minLength".into(), json!(minlength));
+    }
+    if let Some(maxlength) = field["maxlength"].as_u64().filter(|val| *val > 0) {
+        schema.insert("maxLength".into(), json!(maxlength));
+    }
+    if let Some(regex) = field["regex"].as_str().filter(|val| !val.is_empty()) {
+        schema.insert("pattern".into(), json!(regex));
+    }
+    // Hint: For date fields, `min` and `max` are strings.
+    if field["min"].is_number() {
+        schema.insert("minimum".into(), field["min"].clone());
+    }
+    if field["max"].is_number() {
+        schema.insert("maximum".into(), field["max"].clone());
+    }
+}
+
+/// Get the properties of the value of a file field ( FileData, ImageData ).
+fn file_properties(field_type: &str) -> Value {
+    let mut properties = json!({
+        "path": {"type": "string"},
+        "url": {"type": "string", "format": "uri-reference"},
+        "name": {"type": "string"},
+        "size": {"type": "number", "format": "double"},
+        "checksum": {"type": "string"},
+        "is_delete": {"type": "boolean"},
+    });
+    if field_type == "ImageField" {
+        let image_properties = json!({
+            "path_xs": {"type": "string"},
+            "path_sm": {"type": "string"},
+            "path_md": {"type": "string"},
+            "path_lg": {"type": "string"},
+            "url_xs": {"type": "string", "format": "uri-reference"},
+            "url_sm": {"type": "string", "format": "uri-reference"},
+            "url_md": {"type": "string", "format": "uri-reference"},
+            "url_lg": {"type": "string", "format": "uri-reference"},
+            "width": {"type": "number", "format": "double"},
+            "height": {"type": "number", "format": "double"},
+            "blurhash": {"type": "string"},
+            "dominant_color": {"type": "string"},
+        });
+        if let (Value::Object(properties), Value::Object(image_properties)) =
+            (&mut properties, image_properties)
+        {
+            properties.extend(image_properties);
+        }
+    }
+    properties
+}
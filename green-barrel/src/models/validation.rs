@@ -1,13 +1,55 @@
 //! Helper methods to validate data before saving or updating to the database.
 
 use async_trait::async_trait;
+use chrono::NaiveTime;
+use jsonschema::JSONSchema;
 use mongodb::{
-    bson::{doc, oid::ObjectId, Bson, Document},
+    bson::{doc, Bson, Document},
     Collection,
 };
 use regex::{Regex, RegexBuilder};
 use serde_json::value::Value;
-use std::error::Error;
+use std::{error::Error, fs::File, io::Read, path::Path};
+
+use crate::models::password::{char_classes_count, is_common_password};
+
+/// Number of bytes at the beginning of the file that are enough to determine its type.
+pub const FILE_HEAD_SIZE: u64 = 8192;
+
+/// Read the beginning of the file to determine its type
+/// ( without loading the whole file into memory ).
+// *************************************************************************************************
+pub fn read_file_head(path: &Path) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut head = Vec::new();
+    File::open(path)?
+        .take(FILE_HEAD_SIZE)
+        .read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// Validation of password strength - character classes, common passwords, username.
+/// Hint: Parameters with zero or empty values are ignored.
+// *************************************************************************************************
+pub fn check_password_strength(
+    password: &str,
+    field: &Value,
+    username: Option<&str>,
+) -> Vec<String> {
+    let mut err_list = Vec::<String>::new();
+    let min_char_classes = field["min_char_classes"].as_u64().unwrap_or_default() as usize;
+    if min_char_classes > 0 && char_classes_count(password) < min_char_classes {
+        err_list.push(t!("password_char_classes", count = min_char_classes));
+    }
+    if field["is_deny_common"].as_bool().unwrap_or_default() && is_common_password(password) {
+        err_list.push(t!("password_common"));
+    }
+    if let Some(username) = username.map(|val| val.trim().to_lowercase()) {
+        if !username.is_empty() && password.to_lowercase().contains(&username) {
+            err_list.push(t!("password_contains_username"));
+        }
+    }
+    err_list
+}
 
 /// Helper methods to validate data before saving or updating to the database.
 // *************************************************************************************************
@@ -31,6 +73,98 @@ pub trait Validation {
         Ok(())
     }
 
+    /// Validation of `min_items` and `max_items`.
+    // ---------------------------------------------------------------------------------------------
+    fn check_items_count(
+        min_items: usize,
+        max_items: usize,
+        count: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        if min_items > 0 && count < min_items {
+            Err(t!("min_items", count = min_items))?
+        }
+        if max_items > 0 && count > max_items {
+            Err(t!("max_items", count = max_items))?
+        }
+        Ok(())
+    }
+
+    /// Validation of the uploaded file by size and content type.
+    /// Hint: The type is determined by the magic bytes, not by the file extension -
+    /// `head` is the beginning of the file ( see `read_file_head()` ), `size` is the file size in bytes.
+    /// Files without magic bytes are `text/plain` if the head is UTF-8 text without NUL bytes,
+    /// otherwise `application/octet-stream`.
+    fn check_file_content(
+        head: &[u8],
+        size: u64,
+        max_size: u64,
+        allowed_types: &[String],
+        is_image: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        if max_size > 0 && size > max_size {
+            Err(t!("file_too_large", size = max_size))?
+        }
+        // Hint: The head can cut off the last UTF-8 character of a text file.
+        let mime_type = if let Some(kind) = infer::get(head) {
+            kind.mime_type()
+        } else if !head.contains(&0)
+            && std::str::from_utf8(head).map_or_else(|err| err.error_len().is_none(), |_| true)
+        {
+            "text/plain"
+        } else {
+            "application/octet-stream"
+        };
+        if is_image && !mime_type.starts_with("image/") {
+            Err(t!("not_image"))?
+        }
+        if !allowed_types.is_empty()
+            && !allowed_types.iter().any(|allowed_type| {
+                if let Some(prefix) = allowed_type.strip_suffix('*') {
+                    mime_type.starts_with(prefix)
+                } else {
+                    allowed_type == mime_type
+                }
+            })
+        {
+            Err(t!("file_type_not_allowed", mime_type = mime_type))?
+        }
+        Ok(())
+    }
+
+    /// Validation of image dimensions - width, height and aspect ratio ( width / height ).
+    /// Hint: Limits with zero values are ignored.
+    fn check_image_dimensions(dimensions: (u32, u32), field: &Value) -> Vec<String> {
+        let (width, height) = dimensions;
+        let mut err_list = Vec::<String>::new();
+        let limit = |name: &str| field[name].as_u64().unwrap_or_default() as u32;
+        let (min_width, max_width) = (limit("min_width"), limit("max_width"));
+        let (min_height, max_height) = (limit("min_height"), limit("max_height"));
+        if min_width > 0 && width < min_width {
+            err_list.push(t!("min_width", count = min_width));
+        }
+        if max_width > 0 && width > max_width {
+            err_list.push(t!("max_width", count = max_width));
+        }
+        if min_height > 0 && height < min_height {
+            err_list.push(t!("min_height", count = min_height));
+        }
+        if max_height > 0 && height > max_height {
+            err_list.push(t!("max_height", count = max_height));
+        }
+        if height > 0 {
+            let aspect_ratio = width as f64 / height as f64;
+            let min_aspect_ratio = field["min_aspect_ratio"].as_f64().unwrap_or_default();
+            let max_aspect_ratio = field["max_aspect_ratio"].as_f64().unwrap_or_default();
+            if min_aspect_ratio > 0.0 && aspect_ratio < min_aspect_ratio {
+                err_list.push(t!("min_aspect_ratio", ratio = min_aspect_ratio));
+            }
+            if max_aspect_ratio > 0.0 && aspect_ratio > max_aspect_ratio {
+                err_list.push(t!("max_aspect_ratio", ratio = max_aspect_ratio));
+            }
+        }
+        err_list
+    }
+
     /// Accumulation of errors.
     // ---------------------------------------------------------------------------------------------
     fn accumula_err(field: &mut Value, err: &str) {
@@ -90,15 +224,14 @@ pub trait Validation {
     /// Validation of `unique`.
     // ---------------------------------------------------------------------------------------------
     async fn check_unique(
-        hash: &str,
+        doc_id: Option<&Bson>,
         field_name: &str,
         field_value_bson: &Bson,
         coll: &Collection<Document>,
     ) -> Result<(), Box<dyn Error>> {
         //
-        let object_id = ObjectId::parse_str(hash);
         let mut filter = doc! { field_name: field_value_bson };
-        if let Ok(id) = object_id {
+        if let Some(id) = doc_id {
             // If the document is will updated.
             filter = doc! {
                 "$and": [
@@ -114,6 +247,30 @@ pub trait Validation {
         Ok(())
     }
 
+    /// Parse the time of day.
+    /// Formats: `hh:mm` or `hh:mm:ss`.
+    // ---------------------------------------------------------------------------------------------
+    fn parse_time(value: &str) -> Result<NaiveTime, Box<dyn Error>> {
+        let value = value.trim();
+        if let Ok(time) = NaiveTime::parse_from_str(value, "%H:%M:%S") {
+            return Ok(time);
+        }
+        Ok(NaiveTime::parse_from_str(value, "%H:%M")?)
+    }
+
+    /// Validation field attribute `json_schema`.
+    /// Returns a list of schema violations ( empty if the value is valid ).
+    // ---------------------------------------------------------------------------------------------
+    fn json_schema_validation(value: &Value, schema: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        let schema = serde_json::from_str::<Value>(schema)?;
+        let compiled = JSONSchema::compile(&schema).map_err(|err| err.to_string())?;
+        let err_list = match compiled.validate(value) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.map(|err| err.to_string()).collect(),
+        };
+        Ok(err_list)
+    }
+
     /// Validation field attribute `regex`.
     // ----------------------------------------------------------------------------------------------
     fn regex_validation(field_value: &str, regex_str: &str) -> Result<(), Box<dyn Error>> {
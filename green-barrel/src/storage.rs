@@ -0,0 +1,349 @@
+//! Storage backends for files and images ( FileField and ImageField ).
+//!
+//! By default, files are stored in the local file system ( `media_root`/`media_url` field parameters ).
+//! To use another backend, register it under a name and specify this name
+//! in the `storage` field parameter.
+//! The name `gridfs` is reserved - files are stored in the GridFS bucket `media`
+//! in the database of the Model, and FileData/ImageData hold GridFS identifiers in the `path` field.
+//!
+//! # Example:
+//!
+//! ```
+//! let s3 = S3Storage::new(
+//!     "media",
+//!     "us-east-1",
+//!     "http://localhost:9000",
+//!     "minioadmin",
+//!     "minioadmin",
+//!     None,
+//! )?;
+//! register_storage("s3", s3).await;
+//!
+//! // In the model:
+//! avatar: ImageField {
+//!     storage: "s3".into(),
+//!     ..Default::default()
+//! },
+//! photo: ImageField {
+//!     storage: "gridfs".into(),
+//!     ..Default::default()
+//! },
+//! ```
+
+use async_trait::async_trait;
+use futures::io::Cursor;
+use mongodb::{
+    bson::{doc, oid::ObjectId, Bson, Document},
+    error::{ErrorKind, GridFsErrorKind},
+    gridfs::{FilesCollectionDocument, GridFsBucket, GridFsDownloadStream},
+    options::GridFsBucketOptions,
+    Client, Collection,
+};
+use s3::{creds::Credentials, Bucket, Region};
+use sha2::{Digest, Sha256};
+use std::{error::Error, fs, path::Path, sync::Arc};
+
+use crate::store::STORAGES;
+
+/// Operations on objects in storage.
+/// Hint: `path` is the location of the object that is returned by the `put` method
+/// and stored in FileData/ImageData.
+// *************************************************************************************************
+#[async_trait(?Send)]
+pub trait Storage {
+    /// Save the object under the key ( relative path ).
+    /// Returns the path to the object in storage.
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<String, Box<dyn Error>>;
+
+    /// Get the contents of the object.
+    async fn get(&self, path: &str) -> Result<Vec<u8>, Box<dyn Error>>;
+
+    /// Delete the object.
+    async fn delete(&self, path: &str) -> Result<(), Box<dyn Error>>;
+
+    /// Get URL address of the object.
+    fn url(&self, path: &str) -> String;
+}
+
+/// Local file system.
+// *************************************************************************************************
+#[derive(Clone, Debug)]
+pub struct LocalStorage {
+    /// Root partition for storing files.
+    /// Example: "./resources/media".
+    pub media_root: String,
+    /// Url address to the root section.
+    /// Example: "/media".
+    pub media_url: String,
+}
+
+impl LocalStorage {
+    pub fn new(media_root: &str, media_url: &str) -> Self {
+        Self {
+            media_root: media_root.to_string(),
+            media_url: media_url.to_string(),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<String, Box<dyn Error>> {
+        let path = format!("{}/{key}", self.media_root);
+        if let Some(dir_path) = Path::new(path.as_str()).parent() {
+            fs::create_dir_all(dir_path)?;
+        }
+        fs::write(path.as_str(), data)?;
+        Ok(path)
+    }
+
+    async fn get(&self, path: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(fs::read(path)?)
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let path = Path::new(path);
+        if path.is_file() {
+            fs::remove_file(path)?;
+        }
+        // Remove the directory if it is empty ( for example, the image directory ).
+        if let Some(dir_path) = path.parent() {
+            if dir_path.is_dir() && dir_path.read_dir()?.next().is_none() {
+                fs::remove_dir(dir_path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn url(&self, path: &str) -> String {
+        let key = path
+            .strip_prefix(self.media_root.as_str())
+            .unwrap_or(path)
+            .trim_start_matches('/');
+        format!("{}/{key}", self.media_url)
+    }
+}
+
+/// S3-compatible object storage ( AWS S3, MinIO, etc. ).
+// *************************************************************************************************
+#[derive(Clone, Debug)]
+pub struct S3Storage {
+    bucket: Bucket,
+    /// Url address for public access to objects.
+    /// Default: `{endpoint}/{bucket_name}`.
+    pub public_url: String,
+}
+
+impl S3Storage {
+    /// Hint: Path-style requests are used ( `{endpoint}/{bucket_name}/{key}` ).
+    pub fn new(
+        bucket_name: &str,
+        region: &str,
+        endpoint: &str,
+        access_key: &str,
+        secret_key: &str,
+        public_url: Option<&str>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let endpoint = endpoint.trim_end_matches('/');
+        let region = Region::Custom {
+            region: region.to_string(),
+            endpoint: endpoint.to_string(),
+        };
+        let credentials = Credentials::new(Some(access_key), Some(secret_key), None, None, None)?;
+        let bucket = Bucket::new(bucket_name, region, credentials)?.with_path_style();
+        let public_url = if let Some(public_url) = public_url {
+            public_url.trim_end_matches('/').to_string()
+        } else {
+            format!("{endpoint}/{bucket_name}")
+        };
+        Ok(Self { bucket, public_url })
+    }
+}
+
+#[async_trait(?Send)]
+impl Storage for S3Storage {
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<String, Box<dyn Error>> {
+        self.bucket.put_object(key, data.as_slice()).await?;
+        Ok(key.to_string())
+    }
+
+    async fn get(&self, path: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(self.bucket.get_object(path).await?.bytes().to_vec())
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        self.bucket.delete_object(path).await?;
+        Ok(())
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{path}", self.public_url)
+    }
+}
+
+/// Name of the storage backend for GridFS ( `storage` parameter of FileField and ImageField ).
+pub const GRIDFS_STORAGE: &str = "gridfs";
+/// Name of the GridFS bucket in the database of the Model.
+pub const GRIDFS_BUCKET: &str = "media";
+/// Name of the collection with reference counters of content-addressed files
+/// ( `is_content_addressed` parameter of FileField and ImageField ),
+/// in the database of the Model.
+pub const MEDIA_REFS: &str = "media_refs";
+
+/// GridFS bucket in MongoDB.
+/// Hint: `path` is the hex string of the GridFS file identifier.
+// *************************************************************************************************
+#[derive(Clone, Debug)]
+pub struct GridFsStorage {
+    bucket: GridFsBucket,
+    /// Url address for serving files.
+    /// Example: "/media" -> "/media/64d9e4b2c7a3f1a2b3c4d5e6".
+    pub media_url: String,
+}
+
+impl GridFsStorage {
+    pub fn new(client: &Client, database_name: &str, bucket_name: &str, media_url: &str) -> Self {
+        let options = GridFsBucketOptions::builder()
+            .bucket_name(bucket_name.to_string())
+            .build();
+        Self {
+            bucket: client.database(database_name).gridfs_bucket(options),
+            media_url: media_url.to_string(),
+        }
+    }
+
+    /// Get the GridFS file identifier from path.
+    fn file_id(path: &str) -> Result<Bson, Box<dyn Error>> {
+        if let Ok(id) = ObjectId::parse_str(path) {
+            Ok(Bson::ObjectId(id))
+        } else {
+            Err(format!(
+                "Path: `{path}` ; Method: `GridFsStorage::file_id()` => \
+                 The path is not a GridFS file identifier."
+            ))?
+        }
+    }
+
+    /// Get information about the file ( filename, length, upload date ).
+    pub async fn file_info(
+        &self,
+        path: &str,
+    ) -> Result<Option<FilesCollectionDocument>, Box<dyn Error>> {
+        let filter = doc! {"_id": Self::file_id(path)?};
+        let mut cursor = self.bucket.find(filter, None).await?;
+        if cursor.advance().await? {
+            Ok(Some(cursor.deserialize_current()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Open a stream for reading the contents of the file ( for serving files ).
+    /// Hint: The stream implements `futures::io::AsyncRead`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let storage = GridFsStorage::new(&client, "app_name_db", GRIDFS_BUCKET, "/media");
+    /// let mut stream = storage.open_download_stream(&image_data.path).await?;
+    /// let mut buf = Vec::new();
+    /// stream.read_to_end(&mut buf).await?;
+    /// ```
+    ///
+    pub async fn open_download_stream(
+        &self,
+        path: &str,
+    ) -> Result<GridFsDownloadStream, Box<dyn Error>> {
+        Ok(self
+            .bucket
+            .open_download_stream(Self::file_id(path)?)
+            .await?)
+    }
+}
+
+#[async_trait(?Send)]
+impl Storage for GridFsStorage {
+    async fn put(&self, key: &str, data: Vec<u8>) -> Result<String, Box<dyn Error>> {
+        let id = self
+            .bucket
+            .upload_from_futures_0_3_reader(key, Cursor::new(data), None)
+            .await?;
+        Ok(id.to_hex())
+    }
+
+    async fn get(&self, path: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut data = Vec::new();
+        self.bucket
+            .download_to_futures_0_3_writer(Self::file_id(path)?, &mut data)
+            .await?;
+        Ok(data)
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        if let Err(err) = self.bucket.delete(Self::file_id(path)?).await {
+            // The file has already been deleted.
+            if !matches!(
+                *err.kind,
+                ErrorKind::GridFs {
+                    0: GridFsErrorKind::FileNotFound { .. },
+                    ..
+                }
+            ) {
+                Err(err)?
+            }
+        }
+        Ok(())
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{path}", self.media_url)
+    }
+}
+
+/// Get the collection with reference counters of content-addressed files
+/// ( in the database of the Model ).
+// *************************************************************************************************
+pub fn media_refs_coll(coll: &Collection<Document>) -> Collection<Document> {
+    coll.client()
+        .database(coll.namespace().db.as_str())
+        .collection::<Document>(MEDIA_REFS)
+}
+
+/// Get SHA-256 of the data in hex format.
+// *************************************************************************************************
+pub fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Register the storage backend under a name.
+/// Hint: The name is specified in the `storage` parameter of FileField and ImageField.
+// *************************************************************************************************
+pub async fn register_storage<T>(name: &str, storage: T)
+where
+    T: Storage + Send + Sync + 'static,
+{
+    let mut storages = STORAGES.lock().await;
+    storages.insert(name.to_string(), Arc::new(storage));
+}
+
+/// Get the storage backend of the field.
+/// Hint: If the name is empty, the local file system is used.
+// *************************************************************************************************
+pub async fn get_storage(
+    name: &str,
+    media_root: &str,
+    media_url: &str,
+) -> Result<Arc<dyn Storage + Send + Sync>, Box<dyn Error>> {
+    if name.is_empty() {
+        return Ok(Arc::new(LocalStorage::new(media_root, media_url)));
+    }
+    let storages = STORAGES.lock().await;
+    if let Some(storage) = storages.get(name) {
+        Ok(storage.clone())
+    } else {
+        Err(format!(
+            "Storage: `{name}` ; Method: `get_storage()` => \
+            The storage is not registered. Hint: Use `register_storage()`."
+        ))?
+    }
+}
@@ -2,14 +2,37 @@
 
 use async_lock::Mutex;
 use lazy_static::lazy_static;
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 
-use crate::models::helpers::Meta;
+use crate::{
+    migration::{convert::FieldConverter, rewrite::RewriteProgressCallback},
+    models::{helpers::Meta, password::Argon2Params},
+    storage::Storage,
+};
 
 lazy_static! {
     // Metadata caching for Models.
     pub static ref METADATA: Mutex<HashMap<String, Meta>> = {
         Mutex::new(HashMap::new())
     };
-
+    // Registered storage backends for files and images.
+    pub static ref STORAGES: Mutex<HashMap<String, Arc<dyn Storage + Send + Sync>>> = {
+        Mutex::new(HashMap::new())
+    };
+    // Registered converters of field values for migration. Key: (model_key, field_name)
+    pub static ref FIELD_CONVERTERS: Mutex<HashMap<(String, String), FieldConverter>> = {
+        Mutex::new(HashMap::new())
+    };
+    // Handler of the progress of the batched rewrite of documents during migration.
+    pub static ref REWRITE_PROGRESS_CALLBACK: Mutex<Option<RewriteProgressCallback>> = {
+        Mutex::new(None)
+    };
+    // Global Argon2 parameters for password hashing.
+    // Hint: Synchronous lock - hashing is performed in synchronous code.
+    pub static ref ARGON2_PARAMS: RwLock<Argon2Params> = {
+        RwLock::new(Argon2Params::default())
+    };
 }
@@ -0,0 +1,54 @@
+// SHARED TEST DATA
+// #################################################################################################
+
+use green_barrel::Meta;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Metadata of the `User` model for the schema tests.
+pub fn user_meta() -> Meta {
+    let fields = [
+        ("username", "TextField", "String"),
+        ("email", "EmailField", "String"),
+        ("age", "U32Field", "u32"),
+        ("color", "ChoiceTextField", "String"),
+        ("tags", "ListTextField", "Vec<String>"),
+        ("password", "PasswordField", "String"),
+        ("confirm_password", "PasswordField", "String"),
+        ("birthday", "DateField", "String"),
+        ("data", "JsonField", "serde_json::Value"),
+        ("hash", "HashField", "String"),
+    ];
+    Meta {
+        model_name: "User".into(),
+        fields_name: fields.iter().map(|item| item.0.to_string()).collect(),
+        ignore_fields: vec!["confirm_password".into()],
+        field_type_map: fields
+            .iter()
+            .map(|item| (item.0.to_string(), item.1.to_string()))
+            .collect(),
+        field_value_type_map: fields
+            .iter()
+            .map(|item| (item.0.to_string(), item.2.to_string()))
+            .collect(),
+        choice_str_map: HashMap::from([(
+            "color".to_string(),
+            vec!["red".to_string(), "green".to_string()],
+        )]),
+        model_json: json!({
+            "username": {"required": true, "label": "Username", "hint": "Latin letters only.",
+                "minlength": 1, "maxlength": 150, "regex": "^[a-z]+$", "default": null},
+            "email": {"required": true, "minlength": 0, "maxlength": 320, "readonly": true},
+            "age": {"required": false, "max": 120, "default": 18},
+            "color": {"required": false},
+            "tags": {"required": false, "minlength": 0, "maxlength": 20, "regex": "",
+                "min_items": 0, "max_items": 5},
+            "password": {"required": true, "minlength": 8, "maxlength": 256, "regex": ""},
+            "confirm_password": {"required": true, "minlength": 8, "maxlength": 256, "regex": ""},
+            "birthday": {"required": false, "min": "1900-01-01", "max": ""},
+            "data": {"required": false, "json_schema": r#"{"$schema": "x", "type": "object"}"#},
+            "hash": {"required": false, "minlength": 12, "maxlength": 12},
+        }),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,53 @@
+// VERSIONED DATA MIGRATIONS
+// #################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use green_barrel::{DataMigration, Monitor, NapalmPolicy};
+    use mongodb::Client;
+    use std::error::Error;
+
+    struct Noop(&'static str);
+
+    #[async_trait(?Send)]
+    impl DataMigration for Noop {
+        fn version(&self) -> &str {
+            self.0
+        }
+        async fn up(&self, _client: &Client) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+    }
+
+    fn monitor() -> Monitor<'static> {
+        Monitor {
+            app_name: "test_app_name",
+            unique_app_key: "Vj4Dm0fJ9YpQ2bTn",
+            model_key_list: Vec::new(),
+            napalm_policy: NapalmPolicy::Refuse,
+        }
+    }
+
+    #[tokio::test]
+    async fn data_migration_versions() {
+        // The client does not connect to the server until the first operation.
+        let client = Client::with_uri_str("mongodb://localhost:27017")
+            .await
+            .unwrap();
+        let migrations: Vec<Box<dyn DataMigration>> =
+            vec![Box::new(Noop("0001_first")), Box::new(Noop("0001_first"))];
+        let err = monitor()
+            .run_data_migrations(&client, &migrations)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("is not unique"));
+        let migrations: Vec<Box<dyn DataMigration>> = vec![Box::new(Noop(""))];
+        assert!(monitor()
+            .rollback_data_migrations(&client, &migrations, 1)
+            .await
+            .is_err());
+        // Irreversible by default.
+        assert!(Noop("0001_first").down(&client).await.is_err());
+    }
+}
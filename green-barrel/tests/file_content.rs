@@ -0,0 +1,59 @@
+// FILE CONTENT ( SIZE AND TYPE BY MAGIC BYTES )
+// #################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use green_barrel::{read_file_head, Validation, FILE_HEAD_SIZE};
+
+    struct Dummy;
+    impl Validation for Dummy {}
+
+    const PNG: &[u8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D,
+    ];
+    const PDF: &[u8] = b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n";
+    const TEXT: &[u8] = b"plain text";
+    const EXE: &[u8] = &[0x4D, 0x5A, 0x90, 0x00, 0x03, 0x00, 0x00, 0x00];
+
+    #[test]
+    fn file_content_validate() {
+        let pdf_only = vec!["application/pdf".to_string()];
+        let images = vec!["image/*".to_string()];
+        let text_only = vec!["text/plain".to_string()];
+        // valids
+        assert!(Dummy::check_file_content(PDF, PDF.len() as u64, 0, &[], false).is_ok());
+        assert!(Dummy::check_file_content(EXE, EXE.len() as u64, 0, &[], false).is_ok());
+        assert!(Dummy::check_file_content(PDF, PDF.len() as u64, 1024, &pdf_only, false).is_ok());
+        assert!(Dummy::check_file_content(PNG, PNG.len() as u64, 0, &images, false).is_ok());
+        assert!(Dummy::check_file_content(PNG, PNG.len() as u64, 0, &[], true).is_ok());
+        assert!(Dummy::check_file_content(TEXT, TEXT.len() as u64, 0, &text_only, false).is_ok());
+        // The head cuts off the last UTF-8 character.
+        let text = "текст".as_bytes();
+        assert!(Dummy::check_file_content(&text[..9], 9, 0, &text_only, false).is_ok());
+        // invalids
+        assert!(Dummy::check_file_content(PDF, PDF.len() as u64, 8, &[], false).is_err());
+        assert!(Dummy::check_file_content(EXE, EXE.len() as u64, 0, &pdf_only, false).is_err());
+        assert!(Dummy::check_file_content(EXE, EXE.len() as u64, 0, &images, false).is_err());
+        assert!(Dummy::check_file_content(TEXT, TEXT.len() as u64, 0, &pdf_only, false).is_err());
+        assert!(Dummy::check_file_content(EXE, EXE.len() as u64, 0, &text_only, false).is_err());
+        assert!(Dummy::check_file_content(TEXT, TEXT.len() as u64, 0, &[], true).is_err());
+        assert!(Dummy::check_file_content(EXE, EXE.len() as u64, 0, &[], true).is_err());
+        assert!(Dummy::check_file_content(PDF, PDF.len() as u64, 0, &[], true).is_err());
+    }
+
+    #[test]
+    fn file_head() {
+        let path = std::env::temp_dir().join("green_barrel_file_head.pdf");
+        let mut data = PDF.to_vec();
+        data.resize(FILE_HEAD_SIZE as usize * 3, 0);
+        std::fs::write(&path, &data).unwrap();
+        let head = read_file_head(&path).unwrap();
+        assert_eq!(head.len() as u64, FILE_HEAD_SIZE);
+        let size = std::fs::metadata(&path).unwrap().len();
+        let pdf_only = vec!["application/pdf".to_string()];
+        assert!(Dummy::check_file_content(&head, size, 0, &pdf_only, false).is_ok());
+        assert!(Dummy::check_file_content(&head, size, FILE_HEAD_SIZE, &pdf_only, false).is_err());
+        std::fs::remove_file(&path).unwrap();
+        assert!(read_file_head(&path).is_err());
+    }
+}
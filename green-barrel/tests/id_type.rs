@@ -0,0 +1,57 @@
+// DOCUMENT IDENTIFIER `_id`
+// #################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use green_barrel::{HashField, IdType};
+    use mongodb::bson::{oid::ObjectId, Bson};
+
+    #[test]
+    fn id_type_object_id() {
+        let hash = ObjectId::new().to_hex();
+        let id = IdType::ObjectId.to_bson(&hash).unwrap();
+        assert!(matches!(id, Bson::ObjectId(_)));
+        assert_eq!(IdType::to_hash(&id).unwrap(), hash);
+        assert!(IdType::ObjectId.to_bson("123").is_err());
+        assert!(IdType::ObjectId.new_id().is_none());
+        assert!(IdType::ObjectId.is_auto());
+    }
+
+    #[test]
+    fn id_type_uuid() {
+        let hash = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        let id = IdType::Uuid.to_bson(hash).unwrap();
+        assert!(matches!(id, Bson::Binary(_)));
+        assert_eq!(IdType::to_hash(&id).unwrap(), hash);
+        assert!(IdType::Uuid.to_bson("67e55044").is_err());
+        let new_id = IdType::Uuid.new_id().unwrap();
+        assert!(IdType::to_hash(&new_id).is_ok());
+        assert!(IdType::Uuid.is_auto());
+    }
+
+    #[test]
+    fn id_type_caller_supplied() {
+        let id = IdType::Str.to_bson("legacy-key").unwrap();
+        assert_eq!(id, Bson::String("legacy-key".into()));
+        assert_eq!(IdType::to_hash(&id).unwrap(), "legacy-key");
+        assert!(!IdType::Str.is_auto());
+        //
+        let id = IdType::I64.to_bson("42").unwrap();
+        assert_eq!(id, Bson::Int64(42));
+        assert_eq!(IdType::to_hash(&id).unwrap(), "42");
+        assert!(IdType::I64.to_bson("forty-two").is_err());
+        assert!(IdType::I64.new_id().is_none());
+        assert!(!IdType::I64.is_auto());
+    }
+
+    #[test]
+    fn hash_field_obj_id() {
+        let obj_id = ObjectId::new();
+        let mut hash = HashField::default();
+        hash.set_obj_id(obj_id);
+        assert_eq!(hash.obj_id().unwrap(), Some(obj_id));
+        // A caller-supplied id that looks like an ObjectId is still not one.
+        hash.id_type = "String".into();
+        assert_eq!(hash.obj_id().unwrap(), None);
+    }
+}
@@ -0,0 +1,169 @@
+// IMAGE PROCESSING
+// #################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use green_barrel::models::imaging::{
+        apply_orientation, encode_image, exif_orientation, image_dimensions, make_thumbnail,
+        placeholders, process_image, ImageOptions,
+    };
+    use green_barrel::Validation;
+    use image::{DynamicImage, GenericImageView, ImageFormat, RgbImage};
+    use serde_json::json;
+
+    struct Dummy;
+    impl Validation for Dummy {}
+
+    fn options(resize_mode: &str, aspect_ratio: (u32, u32)) -> ImageOptions {
+        ImageOptions::from_field(&json!({
+            "is_quality": true,
+            "is_auto_orient": true,
+            "is_strip_metadata": false,
+            "output_format": "",
+            "jpeg_quality": 80,
+            "resize_mode": resize_mode,
+            "aspect_ratio": aspect_ratio,
+            "pad_color": [255, 255, 255, 255],
+            "is_placeholder": false,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn image_options() {
+        let mut field = json!({
+            "is_quality": true,
+            "is_auto_orient": true,
+            "is_strip_metadata": false,
+            "output_format": "webp",
+            "jpeg_quality": 80,
+            "resize_mode": "scale",
+            "aspect_ratio": [1, 1],
+            "pad_color": [255, 255, 255, 255],
+            "is_placeholder": false,
+        });
+        let options = ImageOptions::from_field(&field).unwrap();
+        assert_eq!(options.output_format(ImageFormat::Png), ImageFormat::WebP);
+        assert!(options.is_reencode(1));
+        field["output_format"] = json!("gif");
+        assert!(ImageOptions::from_field(&field).is_err());
+        field["output_format"] = json!("");
+        field["resize_mode"] = json!("stretch");
+        assert!(ImageOptions::from_field(&field).is_err());
+        field["resize_mode"] = json!("fill");
+        field["jpeg_quality"] = json!(0);
+        assert!(ImageOptions::from_field(&field).is_err());
+        field["jpeg_quality"] = json!(80);
+        let options = ImageOptions::from_field(&field).unwrap();
+        assert_eq!(options.output_format(ImageFormat::Png), ImageFormat::Png);
+        assert!(!options.is_reencode(1));
+        assert!(options.is_reencode(6));
+    }
+
+    #[test]
+    fn image_orientation() {
+        let img = DynamicImage::ImageRgb8(RgbImage::new(40, 20));
+        assert_eq!(apply_orientation(img.clone(), 1).dimensions(), (40, 20));
+        assert_eq!(apply_orientation(img.clone(), 3).dimensions(), (40, 20));
+        assert_eq!(apply_orientation(img.clone(), 6).dimensions(), (20, 40));
+        assert_eq!(apply_orientation(img, 8).dimensions(), (20, 40));
+        // No EXIF data.
+        assert_eq!(exif_orientation(b"not an image"), 1);
+    }
+
+    #[test]
+    fn image_thumbnails() {
+        let img = DynamicImage::ImageRgb8(RgbImage::new(400, 200));
+        // scale
+        let thumbnail = make_thumbnail(&img, 100, &options("scale", (1, 1))).unwrap();
+        assert_eq!(thumbnail.dimensions(), (100, 50));
+        assert!(make_thumbnail(&img, 400, &options("scale", (1, 1))).is_none());
+        // fill
+        let thumbnail = make_thumbnail(&img, 100, &options("fill", (1, 1))).unwrap();
+        assert_eq!(thumbnail.dimensions(), (100, 100));
+        let thumbnail = make_thumbnail(&img, 160, &options("fill", (16, 9))).unwrap();
+        assert_eq!(thumbnail.dimensions(), (160, 90));
+        // pad
+        let thumbnail = make_thumbnail(&img, 100, &options("pad", (1, 1))).unwrap();
+        assert_eq!(thumbnail.dimensions(), (100, 100));
+        assert_eq!(thumbnail.get_pixel(50, 0).0, [255, 255, 255, 255]);
+        assert_eq!(thumbnail.get_pixel(50, 50).0, [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn image_encode() {
+        let img = DynamicImage::ImageRgb8(RgbImage::new(16, 16));
+        for format in [ImageFormat::Jpeg, ImageFormat::Png, ImageFormat::WebP] {
+            let data = encode_image(&img, format, 80).unwrap();
+            assert_eq!(image::guess_format(&data).unwrap(), format);
+        }
+    }
+
+    #[tokio::test]
+    async fn image_process() {
+        let img = DynamicImage::ImageRgb8(RgbImage::new(400, 200));
+        let data = encode_image(&img, ImageFormat::Png, 80).unwrap();
+        assert_eq!(image_dimensions(&data, 1).unwrap(), (400, 200));
+        assert_eq!(image_dimensions(&data, 6).unwrap(), (200, 400));
+        let thumbnails = vec![
+            ("lg".to_string(), 1200),
+            ("md".to_string(), 300),
+            ("sm".to_string(), 150),
+            ("xs".to_string(), 50),
+        ];
+        // Without re-encoding.
+        let processed_image = process_image(
+            data.clone(),
+            1,
+            ImageFormat::Png,
+            &options("scale", (1, 1)),
+            &thumbnails,
+        )
+        .await
+        .unwrap();
+        assert_eq!(processed_image.data, data);
+        let size_names = processed_image
+            .thumbnails
+            .iter()
+            .map(|(size_name, _)| size_name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(size_names, vec!["md", "sm", "xs"]);
+        let thumbnail = image::load_from_memory(&processed_image.thumbnails[0].1).unwrap();
+        assert_eq!(thumbnail.dimensions(), (300, 150));
+        // With re-encoding ( rotated ).
+        let processed_image =
+            process_image(data, 6, ImageFormat::Jpeg, &options("scale", (1, 1)), &[])
+                .await
+                .unwrap();
+        let img = image::load_from_memory(&processed_image.data).unwrap();
+        assert_eq!(img.dimensions(), (200, 400));
+        assert!(processed_image.thumbnails.is_empty());
+    }
+
+    #[test]
+    fn image_placeholders() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_pixel(64, 48, image::Rgb([255, 0, 0])));
+        let (blurhash, dominant_color) = placeholders(&img).unwrap();
+        assert_eq!(blurhash.len(), 4 + 2 * 4 * 3);
+        assert_eq!(dominant_color, "#ff0000");
+    }
+
+    #[test]
+    fn image_dimensions_validate() {
+        let field = json!({
+            "min_width": 100,
+            "min_height": 0,
+            "max_width": 1000,
+            "max_height": 500,
+            "min_aspect_ratio": 1.0,
+            "max_aspect_ratio": 2.0,
+        });
+        // valids
+        assert!(Dummy::check_image_dimensions((400, 400), &field).is_empty());
+        assert!(Dummy::check_image_dimensions((800, 400), &field).is_empty());
+        // invalids
+        assert_eq!(Dummy::check_image_dimensions((50, 50), &field).len(), 1);
+        assert_eq!(Dummy::check_image_dimensions((1200, 400), &field).len(), 2);
+        assert_eq!(Dummy::check_image_dimensions((400, 600), &field).len(), 2);
+    }
+}
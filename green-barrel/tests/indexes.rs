@@ -0,0 +1,84 @@
+// INDEXES DERIVED FROM THE MODEL METADATA
+// #################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use green_barrel::{declared_indexes, migration::indexes::parse_duplicate_key_message, Meta};
+    use mongodb::bson::doc;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn indexes_from_meta() {
+        let meta = Meta {
+            fields_name: vec![
+                "username".to_string(),
+                "age".to_string(),
+                "expires".to_string(),
+                "bio".to_string(),
+                "hash".to_string(),
+            ],
+            field_type_map: [
+                ("username", "TextField"),
+                ("age", "I32Field"),
+                ("expires", "DateTimeField"),
+                ("bio", "TextField"),
+                ("hash", "HashField"),
+            ]
+            .iter()
+            .map(|(name, field_type)| (name.to_string(), field_type.to_string()))
+            .collect(),
+            ignore_fields: vec!["bio".to_string()],
+            unique_indexes: vec![vec![("username".to_string(), 1), ("age".to_string(), -1)]],
+            model_json: json!({
+                "username": {"unique": true, "index": true, "sparse": true},
+                "age": {"unique": false, "index": true, "sparse": true},
+                "expires": {"unique": false, "index": false, "sparse": false, "ttl": 3600},
+                "bio": {"unique": true},
+                "hash": {"unique": true},
+            }),
+            ..Default::default()
+        };
+        let indexes = declared_indexes(&meta);
+        let names = indexes
+            .iter()
+            .map(|index| index.options.as_ref().unwrap().name.clone().unwrap())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            names,
+            vec![
+                "gb__username_1__unique",
+                "gb__age_1__sparse",
+                "gb__expires_1__ttl3600",
+                "gb__username_1_age_-1__unique",
+            ]
+        );
+        // Unique indexes skip empty values.
+        let options = indexes[0].options.as_ref().unwrap();
+        assert_eq!(options.unique, Some(true));
+        assert_eq!(
+            options.partial_filter_expression,
+            Some(doc! {"username": {"$type": "string"}})
+        );
+        assert_eq!(
+            indexes[2].options.as_ref().unwrap().expire_after,
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(indexes[3].keys, doc! {"username": 1, "age": -1});
+    }
+
+    #[test]
+    fn duplicate_key_message() {
+        let fields_name = vec!["first_name".to_string(), "last_name".to_string()];
+        let message = "E11000 duplicate key error collection: db.users \
+            index: gb__first_name_1_last_name_1__unique \
+            dup key: { first_name: \"John, x: 1\", last_name: \"Smith\" }";
+        assert_eq!(
+            parse_duplicate_key_message(message, &fields_name).unwrap(),
+            fields_name
+        );
+        assert!(parse_duplicate_key_message("E11000", &fields_name)
+            .unwrap()
+            .is_empty());
+    }
+}
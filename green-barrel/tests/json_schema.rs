@@ -0,0 +1,27 @@
+// JSON SCHEMA
+// #################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use green_barrel::Validation;
+    use serde_json::json;
+
+    struct Dummy;
+    impl Validation for Dummy {}
+
+    #[test]
+    fn json_schema_validate() {
+        let schema = r#"{"type": "object", "required": ["name"], "properties": {"name": {"type": "string"}}}"#;
+        // valids
+        let err_list = Dummy::json_schema_validation(&json!({"name": "Jane"}), schema).unwrap();
+        assert!(err_list.is_empty());
+        // invalids
+        let err_list = Dummy::json_schema_validation(&json!({"age": 32}), schema).unwrap();
+        assert_eq!(err_list.len(), 1);
+        let err_list = Dummy::json_schema_validation(&json!({"name": 32}), schema).unwrap();
+        assert_eq!(err_list.len(), 1);
+        // invalid schema
+        assert!(Dummy::json_schema_validation(&json!({}), "{").is_err());
+        assert!(Dummy::json_schema_validation(&json!({}), r#"{"type": 12}"#).is_err());
+    }
+}
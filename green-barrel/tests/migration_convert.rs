@@ -0,0 +1,82 @@
+// CONVERSION OF FIELD VALUES DURING MIGRATION
+// #################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use green_barrel::{migration::convert::convert_value, Meta};
+    use mongodb::bson::{bson, Bson};
+
+    #[test]
+    fn convert_scalars() {
+        let meta = Meta::default();
+        // valids
+        assert_eq!(
+            convert_value(&bson!(" 42 "), "I64Field", "age", &meta),
+            Ok(bson!(42_i64))
+        );
+        assert_eq!(
+            convert_value(&bson!(42_i64), "I32Field", "age", &meta),
+            Ok(bson!(42_i32))
+        );
+        assert_eq!(
+            convert_value(&bson!(42_i32), "TextField", "age", &meta),
+            Ok(bson!("42"))
+        );
+        assert_eq!(
+            convert_value(&bson!("2.5"), "F64Field", "rate", &meta),
+            Ok(bson!(2.5))
+        );
+        assert_eq!(
+            convert_value(&bson!("true"), "BoolField", "flag", &meta),
+            Ok(bson!(true))
+        );
+        assert_eq!(
+            convert_value(&bson!(["text"]), "TextField", "name", &meta),
+            Ok(bson!("text"))
+        );
+        assert_eq!(
+            convert_value(&Bson::Null, "I64Field", "age", &meta),
+            Ok(Bson::Null)
+        );
+        assert!(matches!(
+            convert_value(&bson!("1970-02-28"), "DateField", "date", &meta),
+            Ok(Bson::DateTime(_))
+        ));
+        assert!(matches!(
+            convert_value(&bson!("1970-02-28T10:30"), "DateTimeField", "date", &meta),
+            Ok(Bson::DateTime(_))
+        ));
+        // invalids
+        assert!(convert_value(&bson!("forty two"), "I64Field", "age", &meta).is_err());
+        assert!(convert_value(&bson!(5_000_000_000_i64), "I32Field", "age", &meta).is_err());
+        assert!(convert_value(&bson!(-1_i64), "U32Field", "age", &meta).is_err());
+        assert!(convert_value(&bson!(["a", "b"]), "TextField", "name", &meta).is_err());
+        assert!(convert_value(&bson!("28.02.1970"), "DateField", "date", &meta).is_err());
+        assert!(convert_value(&bson!("text"), "PasswordField", "name", &meta).is_err());
+    }
+
+    #[test]
+    fn convert_arrays_and_choices() {
+        let mut meta = Meta::default();
+        meta.choice_str_map.insert(
+            "color".to_string(),
+            vec!["red".to_string(), "green".to_string()],
+        );
+        // valids
+        assert_eq!(
+            convert_value(&bson!("red"), "ChoiceTextMultField", "color", &meta),
+            Ok(bson!(["red"]))
+        );
+        assert_eq!(
+            convert_value(&bson!(["red"]), "ChoiceTextField", "color", &meta),
+            Ok(bson!("red"))
+        );
+        assert_eq!(
+            convert_value(&bson!(["1", 2_i32]), "ListI64Field", "numbers", &meta),
+            Ok(bson!([1_i64, 2_i64]))
+        );
+        // invalids
+        assert!(convert_value(&bson!("blue"), "ChoiceTextMultField", "color", &meta).is_err());
+        assert!(convert_value(&bson!(["1", "x"]), "ListI64Field", "numbers", &meta).is_err());
+    }
+}
@@ -0,0 +1,24 @@
+// LEASE-BASED MIGRATION LOCK
+// #################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use green_barrel::{MigrationLock, MIGRATION_LOCK_TTL};
+    use mongodb::Client;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn migration_lock_ttl() {
+        // The client does not connect to the server until the first operation.
+        let client = Client::with_uri_str("mongodb://localhost:27017")
+            .await
+            .unwrap();
+        let db = client.database("test_migration_lock");
+        let err = MigrationLock::try_acquire(&db, "test", Duration::from_millis(1))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("The TTL is too small"));
+        assert!(MIGRATION_LOCK_TTL >= Duration::from_secs(3));
+    }
+}
@@ -0,0 +1,112 @@
+// PASSWORD HASHING AND STRENGTH POLICY
+// #################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use green_barrel::{
+        check_password_strength, hash_password, verify_password_hash, Argon2Params,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn argon2_params() {
+        let params = Argon2Params::for_model("argon2i", 4096, 3);
+        assert_eq!(params.variant, "argon2i");
+        assert_eq!(params.mem_cost, 4096);
+        assert_eq!(params.time_cost, 3);
+        assert_eq!(params.lanes, 1);
+        assert_eq!(Argon2Params::for_model("", 0, 0), Argon2Params::global());
+        // invalids
+        let mut params = Argon2Params {
+            variant: "argon3".to_string(),
+            ..Default::default()
+        };
+        assert!(params.validate().is_err());
+        params.variant = "argon2d".to_string();
+        params.time_cost = 0;
+        assert!(params.validate().is_err());
+        params.time_cost = 1;
+        params.mem_cost = 4;
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn password_hash() {
+        let params = Argon2Params::for_model("argon2i", 1024, 1);
+        let hash = hash_password("UUbd+5KXw^756*uj", &params).unwrap();
+        assert!(hash.starts_with("$argon2i$v=19$m=1024,t=1,p=1$"));
+        assert!(argon2::verify_encoded(&hash, b"UUbd+5KXw^756*uj").unwrap());
+        assert!(!argon2::verify_encoded(&hash, b"12345678").unwrap());
+    }
+
+    #[test]
+    fn password_rehash() {
+        let old_params = Argon2Params::for_model("argon2i", 1024, 1);
+        let new_params = Argon2Params::for_model("argon2id", 2048, 2);
+        let hash = hash_password("UUbd+5KXw^756*uj", &old_params).unwrap();
+        assert!(!old_params.is_outdated(&hash));
+        assert!(new_params.is_outdated(&hash));
+        assert_eq!(
+            verify_password_hash(&hash, "UUbd+5KXw^756*uj", &old_params).unwrap(),
+            (true, false)
+        );
+        assert_eq!(
+            verify_password_hash(&hash, "UUbd+5KXw^756*uj", &new_params).unwrap(),
+            (true, true)
+        );
+        assert_eq!(
+            verify_password_hash(&hash, "12345678", &new_params).unwrap(),
+            (false, false)
+        );
+        assert_eq!(
+            verify_password_hash("", "12345678", &new_params).unwrap(),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn password_pepper() {
+        let params = Argon2Params::for_model("argon2id", 1024, 1);
+        let peppered_params = Argon2Params {
+            pepper: "server-secret".to_string(),
+            ..params.clone()
+        };
+        let hash = hash_password("UUbd+5KXw^756*uj", &peppered_params).unwrap();
+        assert_eq!(
+            verify_password_hash(&hash, "UUbd+5KXw^756*uj", &peppered_params).unwrap(),
+            (true, false)
+        );
+        assert_eq!(
+            verify_password_hash(&hash, "UUbd+5KXw^756*uj", &params).unwrap(),
+            (false, false)
+        );
+        assert!(!format!("{peppered_params:?}").contains("server-secret"));
+        // A hash created before the pepper was introduced.
+        let hash = hash_password("UUbd+5KXw^756*uj", &params).unwrap();
+        assert_eq!(
+            verify_password_hash(&hash, "UUbd+5KXw^756*uj", &peppered_params).unwrap(),
+            (true, true)
+        );
+    }
+
+    #[test]
+    fn password_strength_validate() {
+        let field = json!({
+            "min_char_classes": 3,
+            "is_deny_common": true,
+        });
+        // valids
+        assert!(check_password_strength("UUbd+5KXw^756*uj", &field, Some("john")).is_empty());
+        assert!(check_password_strength("Password1", &json!({}), None).is_empty());
+        // invalids
+        assert_eq!(check_password_strength("abcdefgh", &field, None).len(), 1);
+        assert_eq!(
+            check_password_strength("Password123", &field, None).len(),
+            1
+        );
+        assert_eq!(
+            check_password_strength("xJohn_2024x", &field, Some("john")).len(),
+            1
+        );
+    }
+}
@@ -28,11 +28,11 @@ mod tests {
         assert!(!re.is_match(""));
         assert!(!re.is_match(&"0".repeat(257)));
         // valids
-        assert!(re.is_match(&"@#$%^&+=*!~)("));
-        assert!(re.is_match(&"0123456789"));
-        assert!(re.is_match(&"abcdefghijklmnopqrstuvwxyz"));
-        assert!(re.is_match(&"ABCDEFGHIJKLMNOPQRSTUVWXYZ"));
-        assert!(re.is_match(&"zeDKs9LtfrB7Xm2"));
+        assert!(re.is_match("@#$%^&+=*!~)("));
+        assert!(re.is_match("0123456789"));
+        assert!(re.is_match("abcdefghijklmnopqrstuvwxyz"));
+        assert!(re.is_match("ABCDEFGHIJKLMNOPQRSTUVWXYZ"));
+        assert!(re.is_match("zeDKs9LtfrB7Xm2"));
         assert!(re.is_match(&"0".repeat(256)));
     }
 
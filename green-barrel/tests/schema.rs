@@ -0,0 +1,112 @@
+// JSON SCHEMA AND OPENAPI EXPORT
+// #################################################################################################
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::user_meta;
+    use green_barrel::{
+        model_json_schema, model_openapi_schema, openapi_components, JSON_SCHEMA_DIALECT,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn json_schema_from_meta() {
+        let schema = model_json_schema(&user_meta()).unwrap();
+        let properties = &schema["properties"];
+        assert_eq!(schema["$schema"], json!(JSON_SCHEMA_DIALECT));
+        assert_eq!(schema["title"], json!("User"));
+        assert_eq!(schema["type"], json!("object"));
+        assert_eq!(
+            schema["required"],
+            json!(["username", "email", "password", "confirm_password"])
+        );
+        assert_eq!(
+            properties["username"],
+            json!({"type": "string", "title": "Username", "description": "Latin letters only.",
+                "minLength": 1, "maxLength": 150, "pattern": "^[a-z]+$"})
+        );
+        assert_eq!(
+            properties["email"],
+            json!({"type": "string", "format": "email", "maxLength": 320, "readOnly": true})
+        );
+        assert_eq!(
+            properties["age"],
+            json!({"type": ["integer", "null"], "format": "int64", "minimum": 0, "maximum": 120,
+                "default": 18})
+        );
+        assert_eq!(
+            properties["color"],
+            json!({"type": ["string", "null"], "enum": ["red", "green", null]})
+        );
+        assert_eq!(
+            properties["tags"],
+            json!({"type": ["array", "null"], "items": {"type": "string", "maxLength": 20},
+                "maxItems": 5})
+        );
+        assert_eq!(
+            properties["password"],
+            json!({"type": "string", "format": "password", "minLength": 8, "maxLength": 256,
+                "writeOnly": true})
+        );
+        assert_eq!(properties["confirm_password"]["writeOnly"], json!(true));
+        assert_eq!(
+            properties["birthday"],
+            json!({"type": ["string", "null"], "format": "date"})
+        );
+        assert_eq!(properties["data"], json!({"type": "object"}));
+        assert_eq!(
+            properties["hash"],
+            json!({"type": ["string", "null"], "readOnly": true})
+        );
+    }
+
+    #[test]
+    fn openapi_schema_from_meta() {
+        let schema = model_openapi_schema(&user_meta()).unwrap();
+        let properties = &schema["properties"];
+        assert!(schema.get("$schema").is_none());
+        assert_eq!(
+            properties["color"],
+            json!({"type": "string", "nullable": true, "enum": ["red", "green", null]})
+        );
+        assert_eq!(
+            properties["tags"],
+            json!({"type": "array", "nullable": true, "items": {"type": "string", "maxLength": 20},
+                "maxItems": 5})
+        );
+        assert_eq!(properties["username"]["type"], json!("string"));
+        // components
+        let components = openapi_components([&user_meta()]).unwrap();
+        assert_eq!(components["schemas"]["User"], schema);
+        assert!(openapi_components([&user_meta(), &user_meta()]).is_err());
+    }
+
+    #[test]
+    fn invalid_json_field_schema() {
+        let mut meta = user_meta();
+        meta.model_json["data"]["json_schema"] = json!("{");
+        assert!(model_json_schema(&meta).is_err());
+    }
+
+    #[test]
+    fn json_schema_zero_limits() {
+        // Hint: 0 - no limit.
+        let mut meta = user_meta();
+        meta.model_json["username"]["maxlength"] = json!(0);
+        meta.model_json["tags"]["maxlength"] = json!(0);
+        meta.model_json["tags"]["max_items"] = json!(0);
+        let schema = model_json_schema(&meta).unwrap();
+        let properties = &schema["properties"];
+        assert_eq!(
+            properties["username"],
+            json!({"type": "string", "title": "Username", "description": "Latin letters only.",
+                "minLength": 1, "pattern": "^[a-z]+$"})
+        );
+        assert_eq!(
+            properties["tags"],
+            json!({"type": ["array", "null"], "items": {"type": "string"}})
+        );
+    }
+}
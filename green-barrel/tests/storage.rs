@@ -0,0 +1,90 @@
+// STORAGE BACKENDS
+// #################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use green_barrel::{
+        get_storage, register_storage, sha256_hex, LocalStorage, S3Storage, Storage,
+    };
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn local_storage() {
+        let storage = LocalStorage::new("./resources/media", "/media");
+        let path = storage
+            .put("test_storage/local/file.txt", b"barrel".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(path, "./resources/media/test_storage/local/file.txt");
+        assert_eq!(storage.url(&path), "/media/test_storage/local/file.txt");
+        assert_eq!(storage.get(&path).await.unwrap(), b"barrel".to_vec());
+        storage.delete(&path).await.unwrap();
+        assert!(!Path::new(&path).exists());
+        assert!(!Path::new("./resources/media/test_storage/local").exists());
+        let _ = std::fs::remove_dir("./resources/media/test_storage");
+    }
+
+    // Run against a local MinIO:
+    // docker run -p 9000:9000 minio/minio server /data
+    // and create the bucket ( `S3_TEST_BUCKET`, default `green-barrel-test` ).
+    #[tokio::test]
+    #[ignore = "requires a local MinIO server"]
+    async fn s3_storage() {
+        let env = |name: &str, default: &str| std::env::var(name).unwrap_or(default.to_string());
+        let endpoint = env("S3_TEST_ENDPOINT", "http://localhost:9000");
+        let bucket_name = env("S3_TEST_BUCKET", "green-barrel-test");
+        let storage = S3Storage::new(
+            &bucket_name,
+            &env("S3_TEST_REGION", "us-east-1"),
+            &endpoint,
+            &env("S3_TEST_ACCESS_KEY", "minioadmin"),
+            &env("S3_TEST_SECRET_KEY", "minioadmin"),
+            None,
+        )
+        .unwrap();
+        let path = storage
+            .put("test_storage/s3/file.txt", b"barrel".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(path, "test_storage/s3/file.txt");
+        assert_eq!(
+            storage.url(&path),
+            format!("{endpoint}/{bucket_name}/test_storage/s3/file.txt")
+        );
+        assert_eq!(storage.get(&path).await.unwrap(), b"barrel".to_vec());
+        storage.delete(&path).await.unwrap();
+        assert!(storage.get(&path).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn registered_storage() {
+        assert!(get_storage("unknown", "./resources/media", "/media")
+            .await
+            .is_err());
+        register_storage("backup", LocalStorage::new("./resources/backup", "/backup")).await;
+        let storage = get_storage("backup", "./resources/media", "/media")
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.url("./resources/backup/files/file.txt"),
+            "/backup/files/file.txt"
+        );
+        let storage = get_storage("", "./resources/media", "/media")
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.url("./resources/media/files/file.txt"),
+            "/media/files/file.txt"
+        );
+    }
+
+    #[test]
+    fn content_checksum() {
+        assert_eq!(
+            sha256_hex(b"barrel"),
+            "e8bbe6423a622988680db31fa80a400b1e6eeca803831711afa17cf332c24918"
+        );
+        assert_eq!(sha256_hex(b"barrel"), sha256_hex(b"barrel"));
+        assert_ne!(sha256_hex(b"barrel"), sha256_hex(b"barrel2"));
+    }
+}
@@ -0,0 +1,86 @@
+// $JSONSCHEMA COLLECTION VALIDATOR
+// #################################################################################################
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::user_meta;
+    use green_barrel::json_schema_validator;
+    use mongodb::bson::{doc, Bson};
+    use serde_json::json;
+
+    #[test]
+    fn validator_from_meta() {
+        let validator = json_schema_validator(&user_meta()).unwrap();
+        let schema = validator.get_document("$jsonSchema").unwrap();
+        let properties = schema.get_document("properties").unwrap();
+        assert_eq!(
+            schema.get_array("required").unwrap(),
+            &vec![
+                Bson::from("username"),
+                Bson::from("email"),
+                Bson::from("password")
+            ]
+        );
+        assert_eq!(
+            properties.get_document("username").unwrap(),
+            &doc! {"bsonType": "string", "minLength": 1_i64, "maxLength": 150_i64, "pattern": "^[a-z]+$"}
+        );
+        assert_eq!(
+            properties.get_document("age").unwrap(),
+            &doc! {"bsonType": ["long", "null"], "maximum": 120_i64}
+        );
+        assert_eq!(
+            properties.get_document("color").unwrap(),
+            &doc! {"bsonType": ["string", "null"], "enum": ["red", "green", Bson::Null]}
+        );
+        assert_eq!(
+            properties.get_document("tags").unwrap(),
+            &doc! {
+                "bsonType": ["array", "null"],
+                "items": {"bsonType": "string", "maxLength": 20_i64},
+                "maxItems": 5_i64,
+            }
+        );
+        // The hash of the password is saved.
+        assert_eq!(
+            properties.get_document("password").unwrap(),
+            &doc! {"bsonType": "string"}
+        );
+        assert_eq!(
+            properties.get_document("birthday").unwrap(),
+            &doc! {"bsonType": ["date", "null"]}
+        );
+        assert_eq!(
+            properties.get_document("email").unwrap(),
+            &doc! {"bsonType": "string", "maxLength": 320_i64}
+        );
+        assert!(!properties.contains_key("confirm_password"));
+        assert!(!properties.contains_key("data"));
+        assert!(!properties.contains_key("hash"));
+    }
+
+    #[test]
+    fn validator_zero_limits() {
+        // Hint: 0 - no limit.
+        let mut meta = user_meta();
+        meta.model_json["username"]["maxlength"] = json!(0);
+        meta.model_json["tags"]["maxlength"] = json!(0);
+        meta.model_json["tags"]["max_items"] = json!(0);
+        let validator = json_schema_validator(&meta).unwrap();
+        let properties = validator
+            .get_document("$jsonSchema")
+            .unwrap()
+            .get_document("properties")
+            .unwrap();
+        assert_eq!(
+            properties.get_document("username").unwrap(),
+            &doc! {"bsonType": "string", "minLength": 1_i64, "pattern": "^[a-z]+$"}
+        );
+        assert_eq!(
+            properties.get_document("tags").unwrap(),
+            &doc! {"bsonType": ["array", "null"], "items": {"bsonType": "string"}}
+        );
+    }
+}
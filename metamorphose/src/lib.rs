@@ -16,8 +16,17 @@
 //! | is_up_doc           | true         | Update documents in the database.                                                                    |
 //! | is_del_doc          | true         | Delete documents from the database.                                                                  |
 //! | ignore_fields       | empty string | Fields that are not included in the database (separated by commas).                                  |
+//! | renamed_fields      | empty string | Renamed fields, migration moves the values (separated by commas). Format: `old_name:new_name`.       |
+//! | indexes             | empty string | Compound indexes (separated by semicolons). Format: `field_name:1, field_name:-1; field_name`.       |
+//! | unique_indexes      | empty string | Compound unique indexes, the format is the same as for `indexes`.                                    |
+//! | validation_level    | moderate     | How the `$jsonSchema` validator applies to existing documents - off, strict, moderate.               |
+//! | validation_action   | error        | Action for documents that fail the `$jsonSchema` validator - error, warn.                            |
 //! | is_use_addition     | false        | Allows methods for additional actions and additional validation.                                     |
 //! | is_use_hooks        | false        | Allows hooks methods - **impl Hooks for ModelName**.                                                 |
+//! | id_type             | ObjectId     | Type of document identifier `_id` - ObjectId, UUID, String, I64 ( String, I64 - set via `hash` ).    |
+//! | argon2_variant      | empty string | Argon2 variant for `PasswordField` - argon2d, argon2i, argon2id ( empty string - global parameter ). |
+//! | argon2_mem_cost     | 0            | Argon2 memory cost in KiB for `PasswordField` ( 0 - global parameter, default 19456 ).               |
+//! | argon2_time_cost    | 0            | Argon2 number of iterations for `PasswordField` ( 0 - global parameter, default 2 ).                 |
 //!
 
 use proc_macro::TokenStream;
@@ -33,7 +42,6 @@ use syn::{
 /// Macro for converting Structure to green-barrel Model.
 /// The model can access the database.
 /// The model can create, update, and delete documents in collections.
-
 #[allow(non_snake_case)]
 #[proc_macro_attribute]
 pub fn Model(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -129,6 +137,84 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                             the format - <field_name, field_name>."
                         )
                     }
+                } else if mnv.path.is_ident("renamed_fields") {
+                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                        let mut value = lit_str.value();
+                        value.retain(|chr| !chr.is_whitespace());
+                        for item in value.split(',').filter(|item| !item.is_empty()) {
+                            let names = item.split(':').collect::<Vec<&str>>();
+                            if names.len() != 2 || names[0].is_empty() || names[1].is_empty() {
+                                panic!(
+                                    "Model: `{model_name_str}` => Invalid value for \
+                                    parameter `renamed_fields`. Use the format - \
+                                    <old_name:new_name, old_name:new_name>."
+                                )
+                            }
+                            trans_meta
+                                .renamed_fields
+                                .insert(names[1].to_string(), names[0].to_string());
+                        }
+                    } else {
+                        panic!(
+                            "Model: `{model_name_str}` => Could not determine value for \
+                            parameter `renamed_fields`. Use the type `&str` in \
+                            the format - <old_name:new_name, old_name:new_name>."
+                        )
+                    }
+                } else if mnv.path.is_ident("indexes") || mnv.path.is_ident("unique_indexes") {
+                    let param_name = mnv.path.get_ident().unwrap().to_string();
+                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                        let indexes = parse_indexes(&lit_str.value()).unwrap_or_else(|| {
+                            panic!(
+                                "Model: `{model_name_str}` => Invalid value for \
+                                parameter `{param_name}`. Use the format - \
+                                <field_name:1, field_name:-1; field_name, field_name>."
+                            )
+                        });
+                        if param_name == "indexes" {
+                            trans_meta.indexes = indexes;
+                        } else {
+                            trans_meta.unique_indexes = indexes;
+                        }
+                    } else {
+                        panic!(
+                            "Model: `{model_name_str}` => Could not determine value for \
+                            parameter `{param_name}`. Use the type `&str` in \
+                            the format - <field_name:1, field_name:-1; field_name, field_name>."
+                        )
+                    }
+                } else if mnv.path.is_ident("validation_level") {
+                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                        let value = lit_str.value().trim().to_lowercase();
+                        if !["off", "strict", "moderate"].contains(&value.as_str()) {
+                            panic!(
+                                "Model: `{model_name_str}` => Invalid value for \
+                                parameter `validation_level`. Valid values: off | strict | moderate"
+                            )
+                        }
+                        trans_meta.validation_level = value;
+                    } else {
+                        panic!(
+                            "Model: `{model_name_str}` => Could not determine value for \
+                            parameter `validation_level`. Use the `&str` type."
+                        )
+                    }
+                } else if mnv.path.is_ident("validation_action") {
+                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                        let value = lit_str.value().trim().to_lowercase();
+                        if !["error", "warn"].contains(&value.as_str()) {
+                            panic!(
+                                "Model: `{model_name_str}` => Invalid value for \
+                                parameter `validation_action`. Valid values: error | warn"
+                            )
+                        }
+                        trans_meta.validation_action = value;
+                    } else {
+                        panic!(
+                            "Model: `{model_name_str}` => Could not determine value for \
+                            parameter `validation_action`. Use the `&str` type."
+                        )
+                    }
                 } else if mnv.path.is_ident("is_use_addition") {
                     if let syn::Lit::Bool(lit_bool) = &mnv.lit {
                         trans_meta.is_use_addition = lit_bool.value;
@@ -153,12 +239,64 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                             parameter `is_use_hooks`. Use the `bool` type."
                         )
                     }
+                } else if mnv.path.is_ident("id_type") {
+                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                        let value = lit_str.value().trim().to_string();
+                        if !["ObjectId", "UUID", "String", "I64"].contains(&value.as_str()) {
+                            panic!(
+                                "Model: `{model_name_str}` => Invalid value for \
+                                parameter `id_type`. Valid values: ObjectId | UUID | String | I64"
+                            )
+                        }
+                        trans_meta.id_type = value;
+                    } else {
+                        panic!(
+                            "Model: `{model_name_str}` => Could not determine value for \
+                            parameter `id_type`. Use the `&str` type."
+                        )
+                    }
+                } else if mnv.path.is_ident("argon2_variant") {
+                    if let syn::Lit::Str(lit_str) = &mnv.lit {
+                        let value = lit_str.value().trim().to_lowercase();
+                        if !["argon2d", "argon2i", "argon2id"].contains(&value.as_str()) {
+                            panic!(
+                                "Model: `{model_name_str}` => Invalid value for \
+                                parameter `argon2_variant`. Valid values: argon2d | argon2i | argon2id"
+                            )
+                        }
+                        trans_meta.argon2_variant = value;
+                    } else {
+                        panic!(
+                            "Model: `{model_name_str}` => Could not determine value for \
+                            parameter `argon2_variant`. Use the `&str` type."
+                        )
+                    }
+                } else if mnv.path.is_ident("argon2_mem_cost") {
+                    if let syn::Lit::Int(lit_int) = &mnv.lit {
+                        trans_meta.argon2_mem_cost = lit_int.base10_parse::<u32>().unwrap();
+                    } else {
+                        panic!(
+                            "Model: `{model_name_str}` => Could not determine value for \
+                            parameter `argon2_mem_cost`. Use the `u32` type."
+                        )
+                    }
+                } else if mnv.path.is_ident("argon2_time_cost") {
+                    if let syn::Lit::Int(lit_int) = &mnv.lit {
+                        trans_meta.argon2_time_cost = lit_int.base10_parse::<u32>().unwrap();
+                    } else {
+                        panic!(
+                            "Model: `{model_name_str}` => Could not determine value for \
+                            parameter `argon2_time_cost`. Use the `u32` type."
+                        )
+                    }
                 } else {
                     panic!(
                         "Model: `{model_name_str}` => Invalid parameter! => \
                         Valid Parameters: database | db_client_name | db_query_docs_limit | \
-                        is_add_doc | is_up_doc | is_del_doc | ignore_fields | \
-                        is_use_add_valid | is_use_hooks"
+                        is_add_doc | is_up_doc | is_del_doc | ignore_fields | renamed_fields | \
+                        indexes | unique_indexes | validation_level | validation_action | \
+                        is_use_add_valid | is_use_hooks | id_type | \
+                        argon2_variant | argon2_mem_cost | argon2_time_cost"
                     )
                 }
             } else {
@@ -232,11 +370,8 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                 }
                 // Add field name and field value type to map.
                 if let Path(ty) = &field.ty {
-                    field_type = {
-                        let tmp_str = quote! {#ty}.to_string();
-                        let tmp_vec = tmp_str.split("::").collect::<Vec<&str>>();
-                        tmp_vec[tmp_vec.len() - 1].trim().to_string()
-                    };
+                    // Hint: Generic arguments are discarded - `ChoiceEnumField<Color>`.
+                    field_type = ty.path.segments.last().unwrap().ident.to_string();
                     let field_info = get_field_info(
                         model_name_str.as_str(),
                         field_name.as_str(),
@@ -277,11 +412,42 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
             )
         }
     }
+    // Checking the field names of compound indexes.
+    for index in trans_meta
+        .indexes
+        .iter()
+        .chain(trans_meta.unique_indexes.iter())
+    {
+        for (field_name, _) in index.iter() {
+            if !trans_meta.fields_name.contains(field_name)
+                || trans_meta.ignore_fields.contains(field_name)
+            {
+                panic!(
+                    "Model: `{model_name_str}` => Model does not have a field named `{field_name}` \
+                    for the compound index."
+                )
+            }
+        }
+    }
     // trans_meta to Json-line.
     let trans_meta_json = match serde_json::to_string(&trans_meta) {
         Ok(json_line) => json_line,
         Err(err) => panic!("Model: `{model_name_str}` => {0:?}", err),
     };
+    // Type of document identifier for the hash field.
+    let id_type = trans_meta.id_type.clone();
+    // Only for `id_type = "ObjectId"` the hash-line can be converted to ObjectId.
+    let obj_id_body = if trans_meta.id_type == "ObjectId" {
+        quote! {
+            let hash = self.hash.value.clone().unwrap_or_default();
+            if let Ok(obj_id) = mongodb::bson::oid::ObjectId::parse_str(hash.as_str()) {
+                return Ok(Some(obj_id));
+            }
+            Ok(None)
+        }
+    } else {
+        quote! { Ok(None) }
+    };
     // html_id_map to Json-line.
     let html_id_map_json = match serde_json::to_string(&html_id_map) {
         Ok(json_line) => json_line,
@@ -392,6 +558,12 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
                         .get_mut("name")
                         .unwrap() = serde_json::json!(field_name);
                 }
+                // Add the identifier type to the hash field.
+                *instance_json_val
+                    .get_mut("hash")
+                    .unwrap()
+                    .get_mut("id_type")
+                    .unwrap() = serde_json::json!(#id_type);
                 //
                 Ok(instance_json_val)
             }
@@ -475,11 +647,7 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
             /// ObjectId from hash field.
             // -------------------------------------------------------------------------------------
             fn obj_id(&self) -> Result<Option<mongodb::bson::oid::ObjectId>, Box<dyn std::error::Error>> {
-                let hash = self.hash.value.clone().unwrap_or_default();
-                if let Ok(obj_id) = mongodb::bson::oid::ObjectId::parse_str(hash.as_str()) {
-                    return Ok(Some(obj_id));
-                }
-                Ok(None)
+                #obj_id_body
             }
 
             /// ObjectId to hash field.
@@ -549,6 +717,119 @@ fn impl_create_model(args: &Vec<NestedMeta>, ast: &mut DeriveInput) -> TokenStre
     TokenStream::from(output)
 }
 
+// CHOICE ENUM - DERIVE FOR ENUM-BACKED CHOICE FIELDS
+// #################################################################################################
+/// Derive for using a Rust enum with `ChoiceEnumField<E>`.
+/// Only unit variants are supported.
+/// By default, the value and label are the name of the variant.
+///
+/// # Example:
+///
+/// ```
+/// #[derive(ChoiceEnum, Clone, Debug, PartialEq)]
+/// enum Color {
+///     #[choice(value = "red", label = "Red color")]
+///     Red,
+///     Green,
+/// }
+/// ```
+#[proc_macro_derive(ChoiceEnum, attributes(choice))]
+pub fn choice_enum(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    impl_choice_enum(&ast)
+}
+
+// Parsing variants of the enum, creating implementation of methods.
+// *************************************************************************************************
+fn impl_choice_enum(ast: &DeriveInput) -> TokenStream {
+    let enum_name_ident = &ast.ident;
+    let enum_name_str = enum_name_ident.to_string();
+    //
+    let variants = if let syn::Data::Enum(data) = &ast.data {
+        &data.variants
+    } else {
+        panic!("Enum: `{enum_name_str}` => ChoiceEnum can only be derived for enums.")
+    };
+    //
+    let mut variant_idents = Vec::<syn::Ident>::new();
+    let mut values = Vec::<String>::new();
+    let mut labels = Vec::<String>::new();
+    for variant in variants {
+        let variant_name = variant.ident.to_string();
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            panic!(
+                "Enum: `{enum_name_str}` > Variant: `{variant_name}` => \
+                Only unit variants are supported."
+            )
+        }
+        let mut value = variant_name.clone();
+        let mut label = variant_name.clone();
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("choice"))
+        {
+            if let Ok(syn::Meta::List(meta_list)) = attr.parse_meta() {
+                for nested_meta in meta_list.nested.iter() {
+                    if let NestedMeta::Meta(syn::Meta::NameValue(mnv)) = nested_meta {
+                        if let syn::Lit::Str(lit_str) = &mnv.lit {
+                            if mnv.path.is_ident("value") {
+                                value = lit_str.value();
+                                continue;
+                            } else if mnv.path.is_ident("label") {
+                                label = lit_str.value();
+                                continue;
+                            }
+                        }
+                    }
+                    panic!(
+                        "Enum: `{enum_name_str}` > Variant: `{variant_name}` => \
+                        Invalid attribute! => Valid Parameters: value | label ( `&str` type )."
+                    )
+                }
+            } else {
+                panic!(
+                    "Enum: `{enum_name_str}` > Variant: `{variant_name}` => \
+                    Invalid attribute! => Example: #[choice(value = \"red\", label = \"Red\")]"
+                )
+            }
+        }
+        if values.contains(&value) {
+            panic!(
+                "Enum: `{enum_name_str}` > Variant: `{variant_name}` => \
+                Duplicate value `{value}`."
+            )
+        }
+        variant_idents.push(variant.ident.clone());
+        values.push(value);
+        labels.push(label);
+    }
+
+    // Implementation of methods.
+    // *********************************************************************************************
+    let output = quote! {
+        impl green_barrel::ChoiceEnum for #enum_name_ident {
+            fn choices() -> Vec<(String, String)> {
+                vec![#((#values.to_string(), #labels.to_string())),*]
+            }
+            fn value(&self) -> String {
+                match self {
+                    #(Self::#variant_idents => #values.to_string(),)*
+                }
+            }
+            fn from_value(value: &str) -> Option<Self> {
+                match value {
+                    #(#values => Some(Self::#variant_idents),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler.
+    TokenStream::from(output)
+}
+
 // AUXILIARY STRUCTURES AND FUNCTIONS
 // #################################################################################################
 /// Transporting of metadate to implementation of methods.
@@ -570,6 +851,12 @@ struct Meta {
     pub is_use_addition: bool,
     pub is_use_hooks: bool,
     pub is_use_hash_slug: bool,
+    // Type of document identifier `_id`.
+    pub id_type: String,
+    // Argon2 parameters for `PasswordField` ( empty string, 0 - use global parameters ).
+    pub argon2_variant: String,
+    pub argon2_mem_cost: u32,
+    pub argon2_time_cost: u32,
     // <field_name, field_value_type>
     pub field_value_type_map: std::collections::HashMap<String, String>,
     // <field_name, field_type>
@@ -578,6 +865,14 @@ struct Meta {
     pub default_value_map: std::collections::HashMap<String, serde_json::Value>,
     // List of field names that will not be saved to the database
     pub ignore_fields: Vec<String>,
+    // Renamed fields. Format: <new_field_name, old_field_name>
+    pub renamed_fields: std::collections::HashMap<String, String>,
+    // Compound indexes. Format: [[(field_name, direction), ...], ...]
+    pub indexes: Vec<Vec<(String, i32)>>,
+    pub unique_indexes: Vec<Vec<(String, i32)>>,
+    // Options of the `$jsonSchema` collection validator.
+    pub validation_level: String,
+    pub validation_action: String,
     // Choice maps for fields type `choice`. Format: <field_name, choices>
     pub choice_str_map: std::collections::HashMap<String, Vec<String>>,
     pub choice_i32_map: std::collections::HashMap<String, Vec<i32>>,
@@ -604,10 +899,19 @@ impl Default for Meta {
             is_use_addition: false,
             is_use_hooks: false,
             is_use_hash_slug: false,
+            id_type: String::from("ObjectId"),
+            argon2_variant: String::new(),
+            argon2_mem_cost: 0_u32,
+            argon2_time_cost: 0_u32,
             field_value_type_map: std::collections::HashMap::new(),
             field_type_map: std::collections::HashMap::new(),
             default_value_map: std::collections::HashMap::new(),
             ignore_fields: Vec::new(),
+            renamed_fields: std::collections::HashMap::new(),
+            indexes: Vec::new(),
+            unique_indexes: Vec::new(),
+            validation_level: String::from("moderate"),
+            validation_action: String::from("error"),
             choice_str_map: std::collections::HashMap::new(),
             choice_i32_map: std::collections::HashMap::new(),
             choice_i64_map: std::collections::HashMap::new(),
@@ -617,6 +921,32 @@ impl Default for Meta {
     }
 }
 
+/// Parse compound indexes.
+/// Format: "field_name:1, field_name:-1; field_name, field_name" ( the default direction is 1 ).
+// *************************************************************************************************
+fn parse_indexes(value: &str) -> Option<Vec<Vec<(String, i32)>>> {
+    let mut value = value.to_string();
+    value.retain(|chr| !chr.is_whitespace());
+    let mut indexes = Vec::<Vec<(String, i32)>>::new();
+    for index in value.split(';').filter(|item| !item.is_empty()) {
+        let mut keys = Vec::<(String, i32)>::new();
+        for key in index.split(',') {
+            let (field_name, direction) = match key.split_once(':') {
+                Some((field_name, "1")) => (field_name, 1),
+                Some((field_name, "-1")) => (field_name, -1),
+                Some(_) => return None,
+                None => (key, 1),
+            };
+            if field_name.is_empty() || keys.iter().any(|(name, _)| name == field_name) {
+                return None;
+            }
+            keys.push((field_name.to_string(), direction));
+        }
+        indexes.push(keys);
+    }
+    Some(indexes)
+}
+
 /// Get field info.
 // *************************************************************************************************
 fn get_field_info<'a>(
@@ -629,6 +959,7 @@ fn get_field_info<'a>(
         "ColorField" => ("String", "color"),
         "DateField" => ("String", "date"),
         "DateTimeField" => ("String", "datetime"),
+        "TimeField" => ("String", "time"),
         "EmailField" => ("String", "email"),
         "FileField" => ("String", "file"),
         "ImageField" => ("String", "file"),
@@ -643,6 +974,7 @@ fn get_field_info<'a>(
         "URLField" => ("String", "url"),
         "IPField" => ("String", "text"),
         "ChoiceTextField" => ("String", "select"),
+        "ChoiceEnumField" => ("String", "select"),
         "ChoiceTextDynField" => ("String", "select"),
         "ChoiceTextMultField" => ("Vec<String>", "select"),
         "ChoiceTextMultDynField" => ("Vec<String>", "select"),
@@ -662,7 +994,12 @@ fn get_field_info<'a>(
         "ChoiceF64DynField" => ("f64", "select"),
         "ChoiceF64MultField" => ("Vec<f64>", "select"),
         "ChoiceF64MultDynField" => ("Vec<f64>", "select"),
+        "ListTextField" => ("Vec<String>", "text"),
+        "ListI64Field" => ("Vec<i64>", "number"),
+        "ListF64Field" => ("Vec<f64>", "number"),
+        "JsonField" => ("serde_json::Value", "textarea"),
         "HashField" => ("String", "text"),
+        "UUIDField" => ("String", "text"),
         "HiddenDateTimeField" => ("String", "datetime"),
         _ => Err(format!(
             "Model: `{model_name}` > Field: `{field_name}` > Field type: `{field_type}` => \
@@ -21,6 +21,8 @@ async fn run_migration(client: &Client) -> Result<(), Box<dyn Error>> {
         unique_app_key: settings::UNIQUE_APP_KEY,
         // For register models.
         model_key_list: vec![models::User::key()?],
+        // Policy for collections left without a model.
+        napalm_policy: NapalmPolicy::Drop,
     };
     monitor.migrat(client).await?;
 
@@ -1,6 +1,6 @@
 use green_barrel::test_tool::del_test_db;
 use green_barrel::*;
-use metamorphose::Model;
+use metamorphose::{ChoiceEnum, Model};
 use mongodb::Client;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
@@ -33,6 +33,14 @@ mod models {
     use super::*;
     use settings::{APP_NAME, DATABASE_NAME, DB_QUERY_DOCS_LIMIT, SERVICE_NAME, UNIQUE_APP_KEY};
 
+    #[derive(ChoiceEnum, Clone, Debug, PartialEq)]
+    pub enum Color {
+        #[choice(value = "red", label = "Red")]
+        Red,
+        #[choice(value = "green", label = "Green")]
+        Green,
+    }
+
     #[Model]
     #[derive(Serialize, Deserialize, Default)]
     pub struct TestModel {
@@ -40,6 +48,7 @@ mod models {
         //
         pub date: DateField,
         pub datetime: DateTimeField,
+        pub time: TimeField,
         //
         pub file: FileField,
         pub image: ImageField,
@@ -56,6 +65,7 @@ mod models {
         pub select_text_dyn: ChoiceTextDynField,
         pub select_text_mult: ChoiceTextMultField,
         pub select_text_mult_dyn: ChoiceTextMultDynField,
+        pub select_enum: ChoiceEnumField<Color>,
         //
         pub select_i32: ChoiceI32Field,
         pub select_i32_dyn: ChoiceI32DynField,
@@ -85,6 +95,13 @@ mod models {
         pub phone: PhoneField,
         pub url: URLField,
         pub ip: IPField,
+        pub uuid: UUIDField,
+        //
+        pub list_text: ListTextField,
+        pub list_i64: ListI64Field,
+        pub list_f64: ListF64Field,
+        //
+        pub json: JsonField,
     }
 
     impl Control for TestModel {
@@ -126,6 +143,8 @@ mod migration {
             unique_app_key: settings::UNIQUE_APP_KEY,
             // Register models
             model_key_list: get_model_key_list()?,
+            // Policy for collections left without a model.
+            napalm_policy: NapalmPolicy::Drop,
         };
         monitor.migrat(client).await?;
 
@@ -187,6 +206,7 @@ async fn test_check_full_default() -> Result<(), Box<dyn Error>> {
     test_model.checkbox.set(true);
     test_model.date.set("0000-01-01");
     test_model.datetime.set("2039-01-01T00:00");
+    test_model.time.set("23:59");
     test_model.file.set("./some_files/resume.pdf", false, None);
     test_model.image.set("./some_files/avatar.png", false, None);
     test_model.number_i32.set(0);
@@ -200,6 +220,16 @@ async fn test_check_full_default() -> Result<(), Box<dyn Error>> {
     test_model.phone.set("+12029182132");
     test_model.url.set("https://ru.wikipedia.org/wiki/URL");
     test_model.ip.set("192.168.123.132");
+    test_model.uuid.set("67e55044-10b1-426f-9247-bb680e5fe0c8");
+    test_model
+        .list_text
+        .set(vec!["rust".into(), "mongodb".into()]);
+    test_model.list_i64.set(vec![1, 2, 3]);
+    test_model.list_f64.set(vec![1.5, 2.5]);
+    test_model.select_enum.set(models::Color::Green);
+    test_model
+        .json
+        .set(serde_json::json!({"color": "red", "sizes": [1, 2, 3]}));
 
     let output_data = test_model.check(&client, None).await?;
     test_model = output_data.update()?;
@@ -213,6 +243,11 @@ async fn test_check_full_default() -> Result<(), Box<dyn Error>> {
         test_model.slug.get().is_none(),
         "test_model.slug.get() != is_none()"
     );
+    assert_eq!(
+        test_model.select_enum.get(),
+        Some(models::Color::Green),
+        "test_model.select_enum.get() != Some(Color::Green)"
+    );
     assert!(
         output_data.get_doc().unwrap().is_empty(),
         "get_doc() != is_empty()"
@@ -0,0 +1,28 @@
+// The derive must not require the `ChoiceEnum` trait to be imported.
+use metamorphose::ChoiceEnum;
+
+#[derive(ChoiceEnum, Clone, Debug, PartialEq)]
+enum Size {
+    #[choice(value = "s", label = "Small")]
+    Small,
+    Large,
+}
+
+// TEST
+// #################################################################################################
+#[test]
+fn test_choice_enum() {
+    assert_eq!(
+        <Size as green_barrel::ChoiceEnum>::choices(),
+        vec![
+            ("s".to_string(), "Small".to_string()),
+            ("Large".to_string(), "Large".to_string())
+        ]
+    );
+    assert_eq!(green_barrel::ChoiceEnum::value(&Size::Small), "s");
+    assert_eq!(
+        <Size as green_barrel::ChoiceEnum>::from_value("Large"),
+        Some(Size::Large)
+    );
+    assert_eq!(<Size as green_barrel::ChoiceEnum>::from_value("m"), None);
+}
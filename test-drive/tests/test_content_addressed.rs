@@ -0,0 +1,215 @@
+use green_barrel::test_tool::del_test_db;
+use green_barrel::*;
+use metamorphose::Model;
+use mongodb::{bson::doc, Client};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs, path::Path};
+
+mod settings {
+    // Project name.
+    // Valid characters: _ a-z A-Z 0-9
+    // Hint: PROJECT_NAM it is recommended not to change.
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const APP_NAME: &str = "test_app_name";
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const DATABASE_NAME: &str = "test_app_name";
+    // The unique key for this test.
+    // To generate a key (This is not an advertisement): https://randompasswordgen.com/
+    // Valid characters: a-z A-Z 0-9
+    // Size: 16
+    pub const UNIQUE_APP_KEY: &str = "Ca7dR3mQ9xWv2LpN";
+    //
+    pub const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 30
+    // First character: a-z A-Z
+    pub const SERVICE_NAME: &str = "test_service_name";
+}
+
+mod models {
+    use super::*;
+    use settings::{APP_NAME, DATABASE_NAME, DB_QUERY_DOCS_LIMIT, SERVICE_NAME, UNIQUE_APP_KEY};
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        pub file: FileField,
+        pub text: TextField,
+    }
+
+    impl Control for TestModel {
+        fn custom() -> Self {
+            Self {
+                file: FileField {
+                    is_content_addressed: true,
+                    ..Default::default()
+                },
+                text: TextField {
+                    required: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        }
+    }
+}
+
+mod migration {
+    use super::*;
+
+    // Get metadata list
+    pub fn get_model_key_list() -> Result<Vec<String>, Box<dyn Error>> {
+        let model_key_list = vec![models::TestModel::key()?];
+        Ok(model_key_list)
+    }
+
+    // Migration
+    pub async fn run_migration(client: &Client) -> Result<(), Box<dyn Error>> {
+        // Caching metadata.
+        models::TestModel::caching(client).await?;
+
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(
+            client,
+            settings::APP_NAME,
+            settings::UNIQUE_APP_KEY,
+            get_model_key_list()?,
+        )
+        .await?;
+
+        // Monitor initialization.
+        let monitor = Monitor {
+            app_name: settings::APP_NAME,
+            unique_app_key: settings::UNIQUE_APP_KEY,
+            // Register models
+            model_key_list: get_model_key_list()?,
+            // Policy for collections left without a model.
+            napalm_policy: NapalmPolicy::Drop,
+        };
+        monitor.migrat(client).await?;
+
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[tokio::test]
+async fn test_content_addressed() -> Result<(), Box<dyn Error>> {
+    let uri = std::env::var("MONGODB_URI").unwrap_or_else(|_| "mongodb://localhost:27017".into());
+    let client = Client::with_uri_str(uri).await?;
+    //
+    migration::run_migration(&client).await?;
+
+    // =============================================================================================
+    type TestModel = models::TestModel;
+    let database_name = {
+        let metadata = METADATA.lock().await;
+        metadata
+            .get(&TestModel::key()?)
+            .unwrap()
+            .database_name
+            .clone()
+    };
+    let refs_coll = client
+        .database(&database_name)
+        .collection::<mongodb::bson::Document>(MEDIA_REFS);
+    let ref_count = |path: String| {
+        let refs_coll = refs_coll.clone();
+        async move {
+            refs_coll
+                .find_one(doc! {"data.path": path}, None)
+                .await
+                .unwrap()
+                .map(|doc| doc.get_i64("count").unwrap())
+        }
+    };
+    let source_path = "./resources/media/default/no_file.odt";
+    let other_source_path = std::env::temp_dir().join("Ca7dR3mQ9xWv2LpN.txt");
+    fs::write(&other_source_path, "content-addressed")?;
+
+    // The save fails - the stored file is not referenced and is deleted.
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = TestModel::new().await?;
+    test_model.file.set(source_path, false, None);
+    let output_data = test_model.save(&client, None, None).await?;
+    test_model = output_data.update()?;
+    assert!(!output_data.is_valid(), "is_valid() != false");
+    assert!(test_model.file.get().is_none(), "file != None");
+    assert_eq!(refs_coll.count_documents(None, None).await?, 0);
+
+    // Two documents with the same content share one stored file.
+    // ---------------------------------------------------------------------------------------------
+    let mut first_model = TestModel::new().await?;
+    first_model.file.set(source_path, false, None);
+    first_model.text.set("first");
+    let output_data = first_model.save(&client, None, None).await?;
+    first_model = output_data.update()?;
+    assert!(
+        output_data.is_valid(),
+        "is_valid(): {}",
+        output_data.err_msg()
+    );
+    //
+    let mut second_model = TestModel::new().await?;
+    second_model.file.set(source_path, false, None);
+    second_model.text.set("second");
+    let output_data = second_model.save(&client, None, None).await?;
+    second_model = output_data.update()?;
+    assert!(
+        output_data.is_valid(),
+        "is_valid(): {}",
+        output_data.err_msg()
+    );
+    //
+    let shared_path = first_model.file.get().unwrap().path;
+    assert_eq!(second_model.file.get().unwrap().path, shared_path);
+    assert!(shared_path.contains("/sha256/"), "{shared_path}");
+    assert_eq!(ref_count(shared_path.clone()).await, Some(2));
+
+    // Replacing the file releases the old reference.
+    // ---------------------------------------------------------------------------------------------
+    first_model
+        .file
+        .set(other_source_path.to_str().unwrap(), false, None);
+    let output_data = first_model.save(&client, None, None).await?;
+    first_model = output_data.update()?;
+    assert!(
+        output_data.is_valid(),
+        "is_valid(): {}",
+        output_data.err_msg()
+    );
+    let other_path = first_model.file.get().unwrap().path;
+    assert_ne!(other_path, shared_path);
+    assert_eq!(ref_count(shared_path.clone()).await, Some(1));
+    assert_eq!(ref_count(other_path.clone()).await, Some(1));
+    assert!(Path::new(&shared_path).is_file());
+
+    // Deleting the last document that references the file deletes the file.
+    // ---------------------------------------------------------------------------------------------
+    let output_data = second_model.delete(&client, None).await?;
+    assert!(output_data.is_valid(), "{}", output_data.err_msg());
+    assert_eq!(ref_count(shared_path.clone()).await, None);
+    assert!(!Path::new(&shared_path).exists());
+    //
+    let output_data = first_model.delete(&client, None).await?;
+    assert!(output_data.is_valid(), "{}", output_data.err_msg());
+    assert!(!Path::new(&other_path).exists());
+    fs::remove_file(&other_source_path)?;
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        &client,
+        settings::APP_NAME,
+        settings::UNIQUE_APP_KEY,
+        migration::get_model_key_list()?,
+    )
+    .await?;
+
+    Ok(())
+}
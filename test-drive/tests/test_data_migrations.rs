@@ -0,0 +1,275 @@
+use async_trait::async_trait;
+use green_barrel::test_tool::del_test_db;
+use green_barrel::*;
+use metamorphose::Model;
+use mongodb::{
+    bson::{doc, Document},
+    Client, Collection,
+};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, time::Duration};
+
+mod settings {
+    // Project name.
+    // Valid characters: _ a-z A-Z 0-9
+    // Hint: PROJECT_NAM it is recommended not to change.
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const APP_NAME: &str = "test_app_name";
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const DATABASE_NAME: &str = "test_app_name";
+    // The unique key for this test.
+    // To generate a key (This is not an advertisement): https://randompasswordgen.com/
+    // Valid characters: a-z A-Z 0-9
+    // Size: 16
+    pub const UNIQUE_APP_KEY: &str = "Dm3kWq8ZpL4vXc1N";
+    //
+    pub const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 30
+    // First character: a-z A-Z
+    pub const SERVICE_NAME: &str = "test_service_name";
+}
+
+mod models {
+    use super::*;
+    use settings::{APP_NAME, DATABASE_NAME, DB_QUERY_DOCS_LIMIT, SERVICE_NAME, UNIQUE_APP_KEY};
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        pub text: TextField,
+    }
+
+    impl Control for TestModel {
+        fn custom() -> Self {
+            Self {
+                ..Default::default()
+            }
+        }
+    }
+}
+
+// Data migrations write `up:{version}` and `down:{version}` to the log collection.
+mod data_migrations {
+    use super::*;
+
+    pub struct Step {
+        pub version: &'static str,
+        pub log: Collection<Document>,
+        pub is_fail: bool,
+    }
+
+    #[async_trait(?Send)]
+    impl DataMigration for Step {
+        fn version(&self) -> &str {
+            self.version
+        }
+        async fn up(&self, _client: &Client) -> Result<(), Box<dyn Error>> {
+            if self.is_fail {
+                Err("Failed to backfill.")?
+            }
+            self.log
+                .insert_one(doc! {"entry": format!("up:{}", self.version)}, None)
+                .await?;
+            Ok(())
+        }
+        async fn down(&self, _client: &Client) -> Result<(), Box<dyn Error>> {
+            self.log
+                .insert_one(doc! {"entry": format!("down:{}", self.version)}, None)
+                .await?;
+            Ok(())
+        }
+    }
+
+    pub fn steps(
+        log: &Collection<Document>,
+        versions: &[&'static str],
+        fail_version: &str,
+    ) -> Vec<Box<dyn DataMigration>> {
+        versions
+            .iter()
+            .map(|version| {
+                Box::new(Step {
+                    version,
+                    log: log.clone(),
+                    is_fail: *version == fail_version,
+                }) as Box<dyn DataMigration>
+            })
+            .collect()
+    }
+
+    pub async fn log_entries(log: &Collection<Document>) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut entries = Vec::<String>::new();
+        let mut cursor = log.find(None, None).await?;
+        while cursor.advance().await? {
+            entries.push(cursor.current().get_str("entry")?.to_string());
+        }
+        Ok(entries)
+    }
+}
+
+mod migration {
+    use super::*;
+
+    // Get metadata list
+    pub fn get_model_key_list() -> Result<Vec<String>, Box<dyn Error>> {
+        let model_key_list = vec![models::TestModel::key()?];
+        Ok(model_key_list)
+    }
+
+    // Migration
+    pub async fn run_migration(client: &Client) -> Result<(), Box<dyn Error>> {
+        // Caching metadata.
+        models::TestModel::caching(client).await?;
+
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(
+            client,
+            settings::APP_NAME,
+            settings::UNIQUE_APP_KEY,
+            get_model_key_list()?,
+        )
+        .await?;
+
+        // Monitor initialization.
+        let monitor = Monitor {
+            app_name: settings::APP_NAME,
+            unique_app_key: settings::UNIQUE_APP_KEY,
+            // Register models
+            model_key_list: get_model_key_list()?,
+            // Policy for collections left without a model.
+            napalm_policy: NapalmPolicy::Drop,
+        };
+        monitor.migrat(client).await?;
+
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[tokio::test]
+async fn test_data_migrations() -> Result<(), Box<dyn Error>> {
+    let uri = std::env::var("MONGODB_URI").unwrap_or_else(|_| "mongodb://localhost:27017".into());
+    let client = Client::with_uri_str(uri).await?;
+    //
+    migration::run_migration(&client).await?;
+
+    // =============================================================================================
+    let monitor = Monitor {
+        app_name: settings::APP_NAME,
+        unique_app_key: settings::UNIQUE_APP_KEY,
+        model_key_list: migration::get_model_key_list()?,
+        napalm_policy: NapalmPolicy::Drop,
+    };
+    let log: Collection<Document> = {
+        let metadata = METADATA.lock().await;
+        let meta = metadata.get(&models::TestModel::key()?).unwrap();
+        client
+            .database(&meta.database_name)
+            .collection("data_migrations_log")
+    };
+    let versions = ["0002_second", "0001_first", "0003_third"];
+
+    // Migrations are applied in ascending order of versions and recorded.
+    // ---------------------------------------------------------------------------------------------
+    let migrations = data_migrations::steps(&log, &versions, "");
+    let applied = monitor.run_data_migrations(&client, &migrations).await?;
+    assert_eq!(applied, vec!["0001_first", "0002_second", "0003_third"]);
+    assert_eq!(
+        monitor.applied_data_migrations(&client).await?,
+        vec!["0001_first", "0002_second", "0003_third"]
+    );
+    assert_eq!(
+        data_migrations::log_entries(&log).await?,
+        vec!["up:0001_first", "up:0002_second", "up:0003_third"]
+    );
+    // Applied migrations are skipped.
+    assert!(monitor
+        .run_data_migrations(&client, &migrations)
+        .await?
+        .is_empty());
+
+    // Rollback in descending order of versions.
+    // ---------------------------------------------------------------------------------------------
+    let rolled_back = monitor
+        .rollback_data_migrations(&client, &migrations, 2)
+        .await?;
+    assert_eq!(rolled_back, vec!["0003_third", "0002_second"]);
+    assert_eq!(
+        monitor.applied_data_migrations(&client).await?,
+        vec!["0001_first"]
+    );
+    assert_eq!(
+        data_migrations::log_entries(&log).await?[3..],
+        ["down:0003_third", "down:0002_second"]
+    );
+
+    // A failing migration stops the run.
+    // ---------------------------------------------------------------------------------------------
+    log.delete_many(doc! {}, None).await?;
+    let migrations = data_migrations::steps(&log, &versions, "0002_second");
+    let err = monitor
+        .run_data_migrations(&client, &migrations)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Data migration `0002_second` ; Method: `up()` => Failed to backfill."),
+        "{err}"
+    );
+    assert_eq!(
+        monitor.applied_data_migrations(&client).await?,
+        vec!["0001_first"]
+    );
+    assert!(data_migrations::log_entries(&log).await?.is_empty());
+
+    // The lock - a second `try_acquire` returns None while the lease is held.
+    // ---------------------------------------------------------------------------------------------
+    let db = client.database(&monitor.green_tech_name()?);
+    let lock = MigrationLock::try_acquire(&db, MIGRAT_LOCK, Duration::from_secs(60))
+        .await?
+        .unwrap();
+    assert!(
+        MigrationLock::try_acquire(&db, MIGRAT_LOCK, Duration::from_secs(60))
+            .await?
+            .is_none(),
+        "try_acquire() != None"
+    );
+    // Schema migrations share the lock with data migrations.
+    assert!(
+        monitor.try_migrat(&client).await?.is_none(),
+        "try_migrat() != None"
+    );
+    assert!(!lock.is_lost(), "is_lost() != false");
+    lock.release().await?;
+
+    // The lease is lost if another instance takes it over
+    // ( migrations stop between batches ).
+    // ---------------------------------------------------------------------------------------------
+    let lock = MigrationLock::try_acquire(&db, MIGRAT_LOCK, Duration::from_secs(3))
+        .await?
+        .unwrap();
+    db.collection::<Document>(MIGRATION_LOCKS)
+        .delete_one(doc! {"_id": MIGRAT_LOCK}, None)
+        .await?;
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert!(lock.is_lost(), "is_lost() != true");
+    assert!(lock.release().await.is_err(), "release() != Err");
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        &client,
+        settings::APP_NAME,
+        settings::UNIQUE_APP_KEY,
+        migration::get_model_key_list()?,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -172,6 +172,8 @@ mod migration {
             unique_app_key: settings::UNIQUE_APP_KEY,
             // Register models
             model_key_list: get_model_key_list()?,
+            // Policy for collections left without a model.
+            napalm_policy: NapalmPolicy::Drop,
         };
         monitor.migrat(client).await?;
 
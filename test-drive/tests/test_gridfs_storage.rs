@@ -0,0 +1,205 @@
+use futures::io::AsyncReadExt;
+use green_barrel::test_tool::del_test_db;
+use green_barrel::*;
+use metamorphose::Model;
+use mongodb::{bson::Document, Client};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs, path::Path};
+
+mod settings {
+    // Project name.
+    // Valid characters: _ a-z A-Z 0-9
+    // Hint: PROJECT_NAM it is recommended not to change.
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const APP_NAME: &str = "test_app_name";
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const DATABASE_NAME: &str = "test_app_name";
+    // The unique key for this test.
+    // To generate a key (This is not an advertisement): https://randompasswordgen.com/
+    // Valid characters: a-z A-Z 0-9
+    // Size: 16
+    pub const UNIQUE_APP_KEY: &str = "Gf2s9KpW0cLx7TzA";
+    //
+    pub const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 30
+    // First character: a-z A-Z
+    pub const SERVICE_NAME: &str = "test_service_name";
+}
+
+mod models {
+    use super::*;
+    use settings::{APP_NAME, DATABASE_NAME, DB_QUERY_DOCS_LIMIT, SERVICE_NAME, UNIQUE_APP_KEY};
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        pub title: TextField,
+        pub file: FileField,
+    }
+
+    impl Control for TestModel {
+        fn custom() -> Self {
+            Self {
+                title: TextField {
+                    required: true,
+                    ..Default::default()
+                },
+                file: FileField {
+                    storage: GRIDFS_STORAGE.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        }
+    }
+}
+
+mod migration {
+    use super::*;
+
+    // Get metadata list
+    pub fn get_model_key_list() -> Result<Vec<String>, Box<dyn Error>> {
+        let model_key_list = vec![models::TestModel::key()?];
+        Ok(model_key_list)
+    }
+
+    // Migration
+    pub async fn run_migration(client: &Client) -> Result<(), Box<dyn Error>> {
+        // Caching metadata.
+        models::TestModel::caching(client).await?;
+
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(
+            client,
+            settings::APP_NAME,
+            settings::UNIQUE_APP_KEY,
+            get_model_key_list()?,
+        )
+        .await?;
+
+        // Monitor initialization.
+        let monitor = Monitor {
+            app_name: settings::APP_NAME,
+            unique_app_key: settings::UNIQUE_APP_KEY,
+            // Register models
+            model_key_list: get_model_key_list()?,
+            // Policy for collections left without a model.
+            napalm_policy: NapalmPolicy::Drop,
+        };
+        monitor.migrat(client).await?;
+
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[tokio::test]
+async fn test_gridfs_storage() -> Result<(), Box<dyn Error>> {
+    let uri = std::env::var("MONGODB_URI").unwrap_or_else(|_| "mongodb://localhost:27017".into());
+    let client = Client::with_uri_str(uri).await?;
+    //
+    migration::run_migration(&client).await?;
+
+    // =============================================================================================
+    type TestModel = models::TestModel;
+    let database_name = {
+        let metadata = METADATA.lock().await;
+        metadata
+            .get(&TestModel::key()?)
+            .unwrap()
+            .database_name
+            .clone()
+    };
+
+    // put, get, file_info, open_download_stream, delete
+    // ---------------------------------------------------------------------------------------------
+    let storage = GridFsStorage::new(&client, &database_name, GRIDFS_BUCKET, "/media");
+    let path = storage.put("files/file.txt", b"barrel".to_vec()).await?;
+    assert_eq!(path.len(), 24, "put(): path is not an ObjectId");
+    assert_eq!(storage.url(&path), format!("/media/{path}"));
+    assert_eq!(storage.get(&path).await?, b"barrel".to_vec());
+    //
+    let file_info = storage.file_info(&path).await?.unwrap();
+    assert_eq!(file_info.filename.as_deref(), Some("files/file.txt"));
+    assert_eq!(file_info.length, 6);
+    //
+    let mut stream = storage.open_download_stream(&path).await?;
+    let mut buf = Vec::new();
+    stream.read_to_end(&mut buf).await?;
+    assert_eq!(buf, b"barrel".to_vec());
+    //
+    storage.delete(&path).await?;
+    assert!(storage.file_info(&path).await?.is_none());
+    assert!(storage.get(&path).await.is_err());
+    // The file has already been deleted.
+    storage.delete(&path).await?;
+    // The path is not a GridFS file identifier.
+    let err = storage.get("./media/file.txt").await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("=> The path is not a GridFS file identifier."),
+        "{err}"
+    );
+
+    // FileField with `storage: "gridfs"` and open_media_stream
+    // ---------------------------------------------------------------------------------------------
+    let source_data = fs::read("./resources/media/default/no_file.odt")?;
+    //
+    let mut test_model = TestModel::new().await?;
+    test_model.title.set("Title");
+    test_model
+        .file
+        .set("./resources/media/default/no_file.odt", false, None);
+    let source_path = test_model.file.get().unwrap().path;
+    let output_data = test_model.save(&client, None, None).await?;
+    test_model = output_data.update()?;
+    assert!(
+        output_data.is_valid(),
+        "is_valid(): {}",
+        output_data.err_msg()
+    );
+    //
+    let file_data = test_model.file.get().unwrap();
+    assert_eq!(file_data.url, format!("/media/{}", file_data.path));
+    assert_eq!(file_data.size, source_data.len() as f64);
+    assert!(!Path::new(&source_path).exists());
+    //
+    let mut stream = TestModel::open_media_stream(&client, &file_data.path).await?;
+    let mut buf = Vec::new();
+    stream.read_to_end(&mut buf).await?;
+    assert_eq!(buf, source_data);
+
+    // If the validation is negative, the stored GridFS file is deleted
+    // ---------------------------------------------------------------------------------------------
+    let files_coll = client
+        .database(&database_name)
+        .collection::<Document>(&format!("{GRIDFS_BUCKET}.files"));
+    assert_eq!(files_coll.count_documents(None, None).await?, 1);
+    //
+    let mut test_model = TestModel::new().await?;
+    test_model
+        .file
+        .set("./resources/media/default/no_file.odt", false, None);
+    let output_data = test_model.save(&client, None, None).await?;
+    assert!(!output_data.is_valid());
+    assert!(output_data.hash().is_empty());
+    assert_eq!(files_coll.count_documents(None, None).await?, 1);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        &client,
+        settings::APP_NAME,
+        settings::UNIQUE_APP_KEY,
+        migration::get_model_key_list()?,
+    )
+    .await?;
+
+    Ok(())
+}
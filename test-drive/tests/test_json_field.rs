@@ -0,0 +1,203 @@
+use green_barrel::test_tool::del_test_db;
+use green_barrel::*;
+use metamorphose::Model;
+use mongodb::{bson::doc, Client};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::error::Error;
+
+mod settings {
+    // Project name.
+    // Valid characters: _ a-z A-Z 0-9
+    // Hint: PROJECT_NAM it is recommended not to change.
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const APP_NAME: &str = "test_app_name";
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const DATABASE_NAME: &str = "test_app_name";
+    // The unique key for this test.
+    // To generate a key (This is not an advertisement): https://randompasswordgen.com/
+    // Valid characters: a-z A-Z 0-9
+    // Size: 16
+    pub const UNIQUE_APP_KEY: &str = "h8Vb3NqX51pZrD6e";
+    //
+    pub const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 30
+    // First character: a-z A-Z
+    pub const SERVICE_NAME: &str = "test_service_name";
+}
+
+mod models {
+    use super::*;
+    use settings::{APP_NAME, DATABASE_NAME, DB_QUERY_DOCS_LIMIT, SERVICE_NAME, UNIQUE_APP_KEY};
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        pub data: JsonField,
+        pub items: JsonField,
+    }
+
+    impl Control for TestModel {
+        fn custom() -> Self {
+            Self {
+                data: JsonField {
+                    required: true,
+                    json_schema: r#"{"type": "object", "required": ["name"]}"#.into(),
+                    ..Default::default()
+                },
+                items: JsonField {
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        }
+    }
+}
+
+mod migration {
+    use super::*;
+
+    // Get metadata list
+    pub fn get_model_key_list() -> Result<Vec<String>, Box<dyn Error>> {
+        let model_key_list = vec![models::TestModel::key()?];
+        Ok(model_key_list)
+    }
+
+    // Migration
+    pub async fn run_migration(client: &Client) -> Result<(), Box<dyn Error>> {
+        // Caching metadata.
+        models::TestModel::caching(client).await?;
+
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(
+            client,
+            settings::APP_NAME,
+            settings::UNIQUE_APP_KEY,
+            get_model_key_list()?,
+        )
+        .await?;
+
+        // Monitor initialization.
+        let monitor = Monitor {
+            app_name: settings::APP_NAME,
+            unique_app_key: settings::UNIQUE_APP_KEY,
+            // Register models
+            model_key_list: get_model_key_list()?,
+            // Policy for collections left without a model.
+            napalm_policy: NapalmPolicy::Drop,
+        };
+        monitor.migrat(client).await?;
+
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[tokio::test]
+async fn test_json_field() -> Result<(), Box<dyn Error>> {
+    let uri = std::env::var("MONGODB_URI").unwrap_or_else(|_| "mongodb://localhost:27017".into());
+    let client = Client::with_uri_str(uri).await?;
+    //
+    migration::run_migration(&client).await?;
+
+    // =============================================================================================
+    type TestModel = models::TestModel;
+    let data = json!({"name": "Jane", "age": 32, "tags": ["a", "b"], "address": {"city": null}});
+    let items = json!([1, "two", {"three": 3.5}, [true]]);
+
+    // self_to_json_val
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = TestModel::new().await?;
+    test_model.data.set(data.clone());
+    test_model.items.set(items.clone());
+    //
+    let model_json = test_model.self_to_json_val()?;
+    assert_eq!(
+        model_json["data"]["value"], data,
+        "self_to_json_val(): data"
+    );
+    assert_eq!(
+        model_json["items"]["value"], items,
+        "self_to_json_val(): items"
+    );
+
+    // json_for_admin
+    // ---------------------------------------------------------------------------------------------
+    let output_data = test_model.save(&client, None, None).await?;
+    test_model = output_data.update()?;
+    assert!(
+        output_data.is_valid(),
+        "is_valid(): {}",
+        output_data.err_msg()
+    );
+    assert_eq!(test_model.data.get(), Some(data.clone()), "update(): data");
+    assert_eq!(
+        test_model.items.get(),
+        Some(items.clone()),
+        "update(): items"
+    );
+    let admin_json = output_data.json_for_admin()?.unwrap();
+    let admin_fields = admin_json.as_array().unwrap();
+    let admin_value = |field_name: &str| {
+        admin_fields
+            .iter()
+            .find(|field| field["name"] == json!(field_name))
+            .map(|field| field["value"].clone())
+    };
+    assert_eq!(
+        admin_value("data"),
+        Some(data.clone()),
+        "json_for_admin(): data"
+    );
+    assert_eq!(
+        admin_value("items"),
+        Some(items.clone()),
+        "json_for_admin(): items"
+    );
+
+    // one_to_json_val ( find_one_to_json )
+    // ---------------------------------------------------------------------------------------------
+    let filter = doc! {"_id": output_data.obj_id()?.unwrap()};
+    let result = TestModel::find_one_to_json(&client, filter.clone(), None).await?;
+    let model_json = serde_json::from_str::<Value>(&result)?;
+    assert_eq!(
+        model_json["data"]["value"], data,
+        "find_one_to_json(): data"
+    );
+    assert_eq!(
+        model_json["items"]["value"], items,
+        "find_one_to_json(): items"
+    );
+    //
+    let instance = TestModel::find_one_to_instance(&client, filter, None)
+        .await?
+        .unwrap();
+    assert_eq!(
+        instance.data.get(),
+        Some(data),
+        "find_one_to_instance(): data"
+    );
+    assert_eq!(
+        instance.items.get(),
+        Some(items),
+        "find_one_to_instance(): items"
+    );
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        &client,
+        settings::APP_NAME,
+        settings::UNIQUE_APP_KEY,
+        migration::get_model_key_list()?,
+    )
+    .await?;
+
+    Ok(())
+}
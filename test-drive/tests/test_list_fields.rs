@@ -0,0 +1,182 @@
+use green_barrel::test_tool::del_test_db;
+use green_barrel::*;
+use metamorphose::Model;
+use mongodb::Client;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+mod settings {
+    // Project name.
+    // Valid characters: _ a-z A-Z 0-9
+    // Hint: PROJECT_NAM it is recommended not to change.
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const APP_NAME: &str = "test_app_name";
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const DATABASE_NAME: &str = "test_app_name";
+    // The unique key for this test.
+    // To generate a key (This is not an advertisement): https://randompasswordgen.com/
+    // Valid characters: a-z A-Z 0-9
+    // Size: 16
+    pub const UNIQUE_APP_KEY: &str = "Qm4Lw27VxT9c1RkH";
+    //
+    pub const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 30
+    // First character: a-z A-Z
+    pub const SERVICE_NAME: &str = "test_service_name";
+}
+
+mod models {
+    use super::*;
+    use settings::{APP_NAME, DATABASE_NAME, DB_QUERY_DOCS_LIMIT, SERVICE_NAME, UNIQUE_APP_KEY};
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        pub list_text: ListTextField,
+        pub list_i64: ListI64Field,
+        pub list_f64: ListF64Field,
+    }
+
+    impl Control for TestModel {
+        fn custom() -> Self {
+            Self {
+                list_text: ListTextField {
+                    required: true,
+                    regex: "^[a-z]+$".into(),
+                    regex_err_msg: "Only lowercase letters.".into(),
+                    ..Default::default()
+                },
+                list_i64: ListI64Field {
+                    required: true,
+                    min: 0,
+                    max: 10,
+                    ..Default::default()
+                },
+                list_f64: ListF64Field {
+                    min: 0.0,
+                    max: 1.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        }
+    }
+}
+
+mod migration {
+    use super::*;
+
+    // Get metadata list
+    pub fn get_model_key_list() -> Result<Vec<String>, Box<dyn Error>> {
+        let model_key_list = vec![models::TestModel::key()?];
+        Ok(model_key_list)
+    }
+
+    // Migration
+    pub async fn run_migration(client: &Client) -> Result<(), Box<dyn Error>> {
+        // Caching metadata.
+        models::TestModel::caching(client).await?;
+
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(
+            client,
+            settings::APP_NAME,
+            settings::UNIQUE_APP_KEY,
+            get_model_key_list()?,
+        )
+        .await?;
+
+        // Monitor initialization.
+        let monitor = Monitor {
+            app_name: settings::APP_NAME,
+            unique_app_key: settings::UNIQUE_APP_KEY,
+            // Register models
+            model_key_list: get_model_key_list()?,
+            // Policy for collections left without a model.
+            napalm_policy: NapalmPolicy::Drop,
+        };
+        monitor.migrat(client).await?;
+
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[tokio::test]
+async fn test_list_fields() -> Result<(), Box<dyn Error>> {
+    let uri = std::env::var("MONGODB_URI").unwrap_or_else(|_| "mongodb://localhost:27017".into());
+    let client = Client::with_uri_str(uri).await?;
+    //
+    migration::run_migration(&client).await?;
+
+    // =============================================================================================
+    type TestModel = models::TestModel;
+
+    // Invalid items - errors are returned in the fields.
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = TestModel::new().await?;
+    test_model.list_text.set(vec!["abc".into(), "ABC".into()]);
+    test_model.list_i64.set(vec![5, 11]);
+    test_model.list_f64.set(vec![-0.5]);
+    //
+    let output_data = test_model.save(&client, None, None).await?;
+    test_model = output_data.update()?;
+    //
+    assert!(!output_data.is_valid(), "is_valid() != false");
+    assert_eq!(
+        test_model.list_text.errors,
+        vec!["Only lowercase letters.".to_string()]
+    );
+    assert_eq!(test_model.list_i64.errors.len(), 1, "list_i64.errors");
+    assert_eq!(test_model.list_f64.errors.len(), 1, "list_f64.errors");
+    assert!(output_data.hash().is_empty(), "hash() != is_empty()");
+
+    // A required list must not be empty.
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = TestModel::new().await?;
+    test_model.list_text.set(Vec::new());
+    test_model.list_i64.set(vec![1]);
+    //
+    let output_data = test_model.check(&client, None).await?;
+    test_model = output_data.update()?;
+    //
+    assert!(!output_data.is_valid(), "is_valid() != false");
+    assert_eq!(test_model.list_text.errors.len(), 1, "list_text.errors");
+    assert!(test_model.list_i64.errors.is_empty(), "list_i64.errors");
+
+    // Valid items.
+    // ---------------------------------------------------------------------------------------------
+    let mut test_model = TestModel::new().await?;
+    test_model.list_text.set(vec!["abc".into()]);
+    test_model.list_i64.set(vec![0, 10]);
+    test_model.list_f64.set(vec![0.5]);
+    //
+    let output_data = test_model.save(&client, None, None).await?;
+    test_model = output_data.update()?;
+    //
+    assert!(
+        output_data.is_valid(),
+        "is_valid(): {}",
+        output_data.err_msg()
+    );
+    assert!(!test_model.hash.get().unwrap().is_empty(), "hash");
+    assert_eq!(test_model.list_i64.get(), Some(vec![0, 10]));
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        &client,
+        settings::APP_NAME,
+        settings::UNIQUE_APP_KEY,
+        migration::get_model_key_list()?,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,250 @@
+use green_barrel::test_tool::del_test_db;
+use green_barrel::*;
+use metamorphose::Model;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    Client, Collection,
+};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+mod settings {
+    // Project name.
+    // Valid characters: _ a-z A-Z 0-9
+    // Hint: PROJECT_NAM it is recommended not to change.
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const APP_NAME: &str = "test_app_name";
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 20
+    // First character: a-z A-Z
+    pub const DATABASE_NAME: &str = "test_app_name";
+    // The unique key for this test.
+    // To generate a key (This is not an advertisement): https://randompasswordgen.com/
+    // Valid characters: a-z A-Z 0-9
+    // Size: 16
+    pub const UNIQUE_APP_KEY: &str = "Mg5tRw8LpZ2xQa7K";
+    //
+    pub const DB_QUERY_DOCS_LIMIT: u32 = 1000;
+    // Valid characters: _ a-z A-Z 0-9
+    // Max size: 30
+    // First character: a-z A-Z
+    pub const SERVICE_NAME: &str = "test_service_name";
+}
+
+// The first version of the Model.
+mod models_v1 {
+    use super::*;
+    use settings::{APP_NAME, DATABASE_NAME, DB_QUERY_DOCS_LIMIT, SERVICE_NAME, UNIQUE_APP_KEY};
+
+    #[Model]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        pub age: TextField,
+        pub score: TextField,
+    }
+
+    impl Control for TestModel {
+        fn custom() -> Self {
+            Self {
+                ..Default::default()
+            }
+        }
+    }
+}
+
+// The second version of the Model - the field types have changed, `score` is renamed.
+mod models_v2 {
+    use super::*;
+    use settings::{APP_NAME, DATABASE_NAME, DB_QUERY_DOCS_LIMIT, SERVICE_NAME, UNIQUE_APP_KEY};
+
+    #[Model(renamed_fields = "score:points")]
+    #[derive(Serialize, Deserialize, Default)]
+    pub struct TestModel {
+        pub age: I64Field,
+        pub points: I64Field,
+    }
+
+    impl Control for TestModel {
+        fn custom() -> Self {
+            Self {
+                ..Default::default()
+            }
+        }
+    }
+}
+
+mod migration {
+    use super::*;
+
+    // Get metadata list
+    pub fn get_model_key_list() -> Result<Vec<String>, Box<dyn Error>> {
+        let model_key_list = vec![models_v1::TestModel::key()?];
+        Ok(model_key_list)
+    }
+
+    // Monitor initialization.
+    pub fn monitor<'a>() -> Result<Monitor<'a>, Box<dyn Error>> {
+        Ok(Monitor {
+            app_name: settings::APP_NAME,
+            unique_app_key: settings::UNIQUE_APP_KEY,
+            // Register models
+            model_key_list: get_model_key_list()?,
+            // Policy for collections left without a model.
+            napalm_policy: NapalmPolicy::Drop,
+        })
+    }
+
+    // Migration
+    pub async fn run_migration(client: &Client) -> Result<(), Box<dyn Error>> {
+        // Caching metadata.
+        models_v1::TestModel::caching(client).await?;
+
+        // Remove test databases
+        // ( Test databases may remain in case of errors )
+        del_test_db(
+            client,
+            settings::APP_NAME,
+            settings::UNIQUE_APP_KEY,
+            get_model_key_list()?,
+        )
+        .await?;
+
+        monitor()?.migrat(client).await?;
+
+        Ok(())
+    }
+}
+
+// TEST
+// #################################################################################################
+#[tokio::test]
+async fn test_migration() -> Result<(), Box<dyn Error>> {
+    let uri = std::env::var("MONGODB_URI").unwrap_or_else(|_| "mongodb://localhost:27017".into());
+    let client = Client::with_uri_str(uri).await?;
+    //
+    migration::run_migration(&client).await?;
+
+    // =============================================================================================
+    let coll: Collection<Document> = {
+        let metadata = METADATA.lock().await;
+        let meta = metadata.get(&models_v1::TestModel::key()?).unwrap();
+        client
+            .database(&meta.database_name)
+            .collection(&meta.collection_name)
+    };
+    for (age, score) in [("42", "7"), ("forty", "seven")] {
+        let mut test_model = models_v1::TestModel::new().await?;
+        test_model.age.set(age);
+        test_model.score.set(score);
+        let output_data = test_model.save(&client, None, None).await?;
+        assert!(
+            output_data.is_valid(),
+            "is_valid(): {}",
+            output_data.err_msg()
+        );
+    }
+    // The second version of the Model.
+    models_v2::TestModel::caching(&client).await?;
+
+    // Migration plan - the database is not changed.
+    // ---------------------------------------------------------------------------------------------
+    let report = migration::monitor()?.plan(&client).await?;
+    assert!(report.has_changes(), "has_changes() != true");
+    let model_report = &report.models[0];
+    let mut retyped_fields = model_report.retyped_fields.clone();
+    retyped_fields.sort();
+    assert_eq!(
+        retyped_fields,
+        vec![
+            (
+                "age".to_string(),
+                "TextField".to_string(),
+                "I64Field".to_string()
+            ),
+            (
+                "points".to_string(),
+                "TextField".to_string(),
+                "I64Field".to_string()
+            )
+        ]
+    );
+    assert_eq!(
+        model_report.renamed_fields,
+        vec![("score".to_string(), "points".to_string())]
+    );
+    assert_eq!(model_report.renamed_docs, 2);
+    assert_eq!(model_report.affected_docs, 2);
+    // The values of the renamed field are converted too.
+    let mut values = report
+        .unconvertible
+        .iter()
+        .map(|item| (item.field_name.clone(), item.value.clone()))
+        .collect::<Vec<(String, Bson)>>();
+    values.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        values,
+        vec![
+            ("age".to_string(), Bson::String("forty".into())),
+            ("points".to_string(), Bson::String("seven".into()))
+        ]
+    );
+    assert_eq!(coll.count_documents(doc! {"age": "42"}, None).await?, 1);
+    assert_eq!(coll.count_documents(doc! {"score": "7"}, None).await?, 1);
+
+    // Renamed fields are moved, unconvertible values are kept.
+    // ---------------------------------------------------------------------------------------------
+    let report = migration::monitor()?.migrat(&client).await?;
+    assert!(report.has_changes(), "has_changes() != true");
+    assert_eq!(report.unconvertible.len(), 2, "unconvertible");
+    assert_eq!(report.models[0].renamed_docs, 2);
+    assert_eq!(report.models[0].affected_docs, 2);
+    assert_eq!(coll.count_documents(doc! {"age": 42_i64}, None).await?, 1);
+    assert_eq!(coll.count_documents(doc! {"age": "forty"}, None).await?, 1);
+    assert_eq!(coll.count_documents(doc! {"points": 7_i64}, None).await?, 1);
+    assert_eq!(
+        coll.count_documents(doc! {"points": "seven"}, None).await?,
+        1
+    );
+    assert_eq!(
+        coll.count_documents(doc! {"score": {"$exists": true}}, None)
+            .await?,
+        0
+    );
+
+    // NapalmPolicy::Refuse - the database is not changed.
+    // ---------------------------------------------------------------------------------------------
+    let monitor = Monitor {
+        model_key_list: Vec::new(),
+        napalm_policy: NapalmPolicy::Refuse,
+        ..migration::monitor()?
+    };
+    let err = monitor.migrat(&client).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("There are collections left without a model"),
+        "{err}"
+    );
+    let monitor_models = client
+        .database(&monitor.green_tech_name()?)
+        .collection::<Document>("monitor_models");
+    assert_eq!(
+        monitor_models
+            .count_documents(doc! {"status": false}, None)
+            .await?,
+        0
+    );
+    assert_eq!(coll.count_documents(None, None).await?, 2);
+
+    // Delete test database
+    // =============================================================================================
+    del_test_db(
+        &client,
+        settings::APP_NAME,
+        settings::UNIQUE_APP_KEY,
+        migration::get_model_key_list()?,
+    )
+    .await?;
+
+    Ok(())
+}
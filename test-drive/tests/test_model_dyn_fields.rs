@@ -90,6 +90,8 @@ mod migration {
             unique_app_key: settings::UNIQUE_APP_KEY,
             // Register models
             model_key_list: get_model_key_list()?,
+            // Policy for collections left without a model.
+            napalm_policy: NapalmPolicy::Drop,
         };
         monitor.migrat(client).await?;
 
@@ -302,7 +304,10 @@ async fn test_model_dyn_fields() -> Result<(), Box<dyn Error>> {
     };
     //
     assert!(
-        obj_fields_doc.get_array("select_text_dyn").unwrap().len() == 0,
+        obj_fields_doc
+            .get_array("select_text_dyn")
+            .unwrap()
+            .is_empty(),
         "select_text_dyn ; len == 0"
     );
     //
@@ -310,13 +315,15 @@ async fn test_model_dyn_fields() -> Result<(), Box<dyn Error>> {
         obj_fields_doc
             .get_array("select_text_mult_dyn")
             .unwrap()
-            .len()
-            == 0,
+            .is_empty(),
         "select_text_mult_dyn ; len == 0"
     );
     //
     assert!(
-        obj_fields_doc.get_array("select_i32_dyn").unwrap().len() == 0,
+        obj_fields_doc
+            .get_array("select_i32_dyn")
+            .unwrap()
+            .is_empty(),
         "select_i32_dyn ; len == 0"
     );
     //
@@ -324,13 +331,15 @@ async fn test_model_dyn_fields() -> Result<(), Box<dyn Error>> {
         obj_fields_doc
             .get_array("select_i32_mult_dyn")
             .unwrap()
-            .len()
-            == 0,
+            .is_empty(),
         "select_i32_mult_dyn ; len == 0"
     );
     //
     assert!(
-        obj_fields_doc.get_array("select_u32_dyn").unwrap().len() == 0,
+        obj_fields_doc
+            .get_array("select_u32_dyn")
+            .unwrap()
+            .is_empty(),
         "select_u32_dyn ; len == 0"
     );
     //
@@ -338,13 +347,15 @@ async fn test_model_dyn_fields() -> Result<(), Box<dyn Error>> {
         obj_fields_doc
             .get_array("select_u32_mult_dyn")
             .unwrap()
-            .len()
-            == 0,
+            .is_empty(),
         "select_u32_mult_dyn ; len == 0"
     );
     //
     assert!(
-        obj_fields_doc.get_array("select_i64_dyn").unwrap().len() == 0,
+        obj_fields_doc
+            .get_array("select_i64_dyn")
+            .unwrap()
+            .is_empty(),
         "select_i64_dyn ; len == 0"
     );
     //
@@ -352,13 +363,15 @@ async fn test_model_dyn_fields() -> Result<(), Box<dyn Error>> {
         obj_fields_doc
             .get_array("select_i64_mult_dyn")
             .unwrap()
-            .len()
-            == 0,
+            .is_empty(),
         "select_i64_mult_dyn ; len == 0"
     );
     //
     assert!(
-        obj_fields_doc.get_array("select_f64_dyn").unwrap().len() == 0,
+        obj_fields_doc
+            .get_array("select_f64_dyn")
+            .unwrap()
+            .is_empty(),
         "select_f64_dyn ; len == 0"
     );
     //
@@ -366,8 +379,7 @@ async fn test_model_dyn_fields() -> Result<(), Box<dyn Error>> {
         obj_fields_doc
             .get_array("select_f64_mult_dyn")
             .unwrap()
-            .len()
-            == 0,
+            .is_empty(),
         "select_f64_mult_dyn ; len == 0"
     );
     //